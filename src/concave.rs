@@ -0,0 +1,340 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// concave.rs - Concave hulls of point clouds by chi-shapes
+use crate::polygon::SimplePolygon;
+use crate::primitives::Point;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConcaveThreshold {
+    //Boundary edges longer than this are cut away where possible
+    Length(f64),
+    //Between 0 and 1, from the shortest to the longest edge of the Delaunay triangulation. 1 gives
+    //the convex hull
+    Relative(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConcaveHullError {
+    TooFewPoints(usize),
+    DuplicatePoint(Point),
+    //All the points are in line, so they bound no area
+    Collinear,
+}
+
+fn length2(p: &Point, q: &Point) -> i128 {
+    let (dx, dy) = ((q.x - p.x) as i128, (q.y - p.y) as i128);
+    dx * dx + dy * dy
+}
+
+//Positive when d is strictly inside the circle through the counterclockwise triangle abc
+fn in_circle(a: &Point, b: &Point, c: &Point, d: &Point) -> bool {
+    let row = |p: &Point| {
+        let (x, y) = ((p.x - d.x) as i128, (p.y - d.y) as i128);
+        (x, y, x * x + y * y)
+    };
+    let (ax, ay, a2) = row(a);
+    let (bx, by, b2) = row(b);
+    let (cx, cy, c2) = row(c);
+    a2 * (bx * cy - cx * by) - b2 * (ax * cy - cx * ay) + c2 * (ax * by - bx * ay) > 0
+}
+
+//Stands in for the point at infinity, which closes each hull edge into a ghost triangle
+const GHOST: usize = usize::MAX;
+
+//Counterclockwise triangles over point indices in half-edge form. Half-edge 3 t + k runs from
+//vertex k of triangle t to the next one, and twin holds the half-edge running the other way.
+//Every hull edge has a ghost triangle outside it, so every half-edge has a twin
+struct Triangulation<'a> {
+    pts: &'a [Point],
+    triangles: Vec<[usize; 3]>,
+    twin: Vec<usize>,
+}
+
+fn next_edge(e: usize) -> usize {
+    if e % 3 == 2 {
+        e - 2
+    } else {
+        e + 1
+    }
+}
+
+fn prev_edge(e: usize) -> usize {
+    if e.is_multiple_of(3) {
+        e + 2
+    } else {
+        e - 1
+    }
+}
+
+impl<'a> Triangulation<'a> {
+    fn origin(&self, e: usize) -> usize {
+        self.triangles[e / 3][e % 3]
+    }
+
+    fn is_ghost(&self, t: usize) -> bool {
+        self.triangles[t].contains(&GHOST)
+    }
+
+    fn link(&mut self, e: usize, f: usize) {
+        self.twin[e] = f;
+        self.twin[f] = e;
+    }
+
+    fn push(&mut self, t: [usize; 3]) -> usize {
+        self.triangles.push(t);
+        self.twin.extend([GHOST; 3]);
+        self.triangles.len() - 1
+    }
+
+    //Sweeps the points in lexicographic order. Each new point lies outside the hull so far, and
+    //the ghost triangles of the hull edges it sees become real ones with it as their apex, found
+    //by walking both ways from the point before it. Those edges are then flipped as needed, so
+    //that the triangulation stays Delaunay. Fan edges out of the new point need no check, as the
+    //hull bulges towards it at their far ends. The hull is a cycle kept in next and prev, and
+    //ghost holds the ghost triangle of the edge leaving each hull vertex
+    fn sweep(pts: &'a [Point], order: &[usize]) -> Result<Self, ConcaveHullError> {
+        let mut tr = Triangulation {
+            pts,
+            triangles: Vec::new(),
+            twin: Vec::new(),
+        };
+        let (p0, p1) = (order[0], order[1]);
+        let k = (2..order.len())
+            .find(|&k| Point::cross(&pts[p0], &pts[p1], &pts[order[k]]) != 0)
+            .ok_or(ConcaveHullError::Collinear)?;
+        let q = order[k];
+        let n = pts.len();
+        let (mut next, mut prev, mut ghost) = (vec![0; n], vec![0; n], vec![0; n]);
+
+        //The points before q lie on one line, and are fanned to it, which is already Delaunay
+        let mut cycle: Vec<usize> = order[..k].to_vec();
+        let left = Point::cross(&pts[p0], &pts[p1], &pts[q]) > 0;
+        for w in cycle.windows(2) {
+            tr.push(if left {
+                [w[0], w[1], q]
+            } else {
+                [w[1], w[0], q]
+            });
+        }
+        if left {
+            cycle.push(q);
+        } else {
+            cycle[1..].reverse();
+            cycle.insert(1, q);
+        }
+        for i in 0..cycle.len() {
+            let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            next[u] = v;
+            prev[v] = u;
+            ghost[u] = tr.push([v, u, GHOST]);
+        }
+        let mut ends = HashMap::new();
+        for e in 0..tr.twin.len() {
+            let (a, b) = (tr.origin(e), tr.origin(next_edge(e)));
+            match ends.remove(&(b, a)) {
+                Some(f) => tr.link(e, f),
+                None => {
+                    ends.insert((a, b), e);
+                }
+            }
+        }
+
+        let mut last = q;
+        for &p in &order[k + 1..] {
+            let sees = |u: usize, v: usize| Point::cross(&pts[u], &pts[v], &pts[p]) < 0;
+            let mut chain = VecDeque::from([last]);
+            while sees(chain[chain.len() - 1], next[chain[chain.len() - 1]]) {
+                chain.push_back(next[chain[chain.len() - 1]]);
+            }
+            while sees(prev[chain[0]], chain[0]) {
+                chain.push_front(prev[chain[0]]);
+            }
+            let (lo, hi) = (chain[0], chain[chain.len() - 1]);
+            //Ghost triangle v, u, GHOST turns into v, u, p, leaving its twins as they are. New
+            //ghosts go on the hull edges into and out of p
+            let (into_lo, out_of_hi) =
+                (tr.twin[3 * ghost[lo] + 1], tr.twin[3 * ghost[prev[hi]] + 2]);
+            let mut stack = Vec::with_capacity(chain.len());
+            for i in 0..chain.len() - 1 {
+                let g = ghost[chain[i]];
+                tr.triangles[g][2] = p;
+                stack.push(3 * g);
+            }
+            let (gl, gh) = (tr.push([p, lo, GHOST]), tr.push([hi, p, GHOST]));
+            tr.link(3 * gl, 3 * ghost[lo] + 1);
+            tr.link(3 * gl + 1, into_lo);
+            tr.link(3 * gl + 2, 3 * gh + 1);
+            tr.link(3 * gh, 3 * ghost[prev[hi]] + 2);
+            tr.link(3 * gh + 2, out_of_hi);
+            ghost[lo] = gl;
+            ghost[p] = gh;
+            next[lo] = p;
+            prev[p] = lo;
+            next[p] = hi;
+            prev[hi] = p;
+            tr.legalize(stack);
+            last = p;
+        }
+        Ok(tr)
+    }
+
+    //Lawson's flips, starting from the given half-edges. An edge whose far apex lies inside the
+    //circle of the near triangle is swapped for the other diagonal of the quadrilateral, and the
+    //four sides around it are checked in turn
+    fn legalize(&mut self, mut stack: Vec<usize>) {
+        while let Some(e) = stack.pop() {
+            let f = self.twin[e];
+            let (t1, t2) = (e / 3, f / 3);
+            if self.is_ghost(t1) || self.is_ghost(t2) {
+                continue;
+            }
+            let (a, b, c) = (self.origin(e), self.origin(f), self.origin(prev_edge(e)));
+            let d = self.origin(prev_edge(f));
+            let p = self.pts;
+            if !in_circle(&p[a], &p[b], &p[c], &p[d]) {
+                continue;
+            }
+            let (bc, ca) = (self.twin[next_edge(e)], self.twin[prev_edge(e)]);
+            let (ad, db) = (self.twin[next_edge(f)], self.twin[prev_edge(f)]);
+            //The quadrilateral runs a, d, b, c counterclockwise
+            self.triangles[t1] = [a, d, c];
+            self.triangles[t2] = [d, b, c];
+            self.link(3 * t1, ad);
+            self.link(3 * t1 + 1, 3 * t2 + 2);
+            self.link(3 * t1 + 2, ca);
+            self.link(3 * t2, db);
+            self.link(3 * t2 + 1, bc);
+            stack.extend([3 * t1, 3 * t2, 3 * t2 + 1, 3 * t1 + 2]);
+        }
+    }
+}
+
+//Chi-shape of the points (Duckham et al. 2008). The Delaunay triangulation is eroded from
+//outside, longest boundary edge first, by removing the triangle behind an edge above the
+//threshold unless its apex is already on the boundary, which keeps the region a simple polygon
+//holding every point. Points left inside are not vertices of the result, and nor are points
+//where the boundary runs straight on. Exact for coordinates up to about a million
+pub fn concave_hull(
+    points: &[Point],
+    threshold: ConcaveThreshold,
+) -> Result<SimplePolygon, ConcaveHullError> {
+    if points.len() < 3 {
+        return Err(ConcaveHullError::TooFewPoints(points.len()));
+    }
+    let mut seen = HashSet::with_capacity(points.len());
+    if let Some(p) = points.iter().find(|&p| !seen.insert(p)) {
+        return Err(ConcaveHullError::DuplicatePoint(p.clone()));
+    }
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by_key(|&i| (points[i].x, points[i].y));
+    let tr = Triangulation::sweep(points, &order)?;
+
+    let mut alive: Vec<bool> = (0..tr.triangles.len()).map(|t| !tr.is_ghost(t)).collect();
+    let limit = match threshold {
+        ConcaveThreshold::Length(l) => l,
+        ConcaveThreshold::Relative(r) => {
+            let lengths = (0..tr.twin.len())
+                .filter(|&e| alive[e / 3])
+                .map(|e| length2(&points[tr.origin(e)], &points[tr.origin(next_edge(e))]));
+            let (lo, hi) = lengths.fold((i128::MAX, 0), |(lo, hi), l| (lo.min(l), hi.max(l)));
+            let (lo, hi) = ((lo as f64).sqrt(), (hi as f64).sqrt());
+            hi * r + lo * (1.0 - r)
+        }
+    };
+    let is_boundary = |alive: &[bool], e: usize| alive[e / 3] && !alive[tr.twin[e] / 3];
+    let length = |e: usize| length2(&points[tr.origin(e)], &points[tr.origin(next_edge(e))]);
+    let mut on_boundary = vec![false; points.len()];
+    let mut heap = BinaryHeap::new();
+    for e in 0..tr.twin.len() {
+        if is_boundary(&alive, e) {
+            on_boundary[tr.origin(e)] = true;
+            heap.push((length(e), e));
+        }
+    }
+    while let Some((l, e)) = heap.pop() {
+        if (l as f64).sqrt() <= limit {
+            break;
+        }
+        //Entries go stale once their triangle is gone
+        if !is_boundary(&alive, e) {
+            continue;
+        }
+        let c = tr.origin(prev_edge(e));
+        if on_boundary[c] {
+            continue;
+        }
+        alive[e / 3] = false;
+        on_boundary[c] = true;
+        for f in [tr.twin[next_edge(e)], tr.twin[prev_edge(e)]] {
+            heap.push((length(f), f));
+        }
+    }
+
+    //The boundary edges run counterclockwise around the region, and form a single cycle
+    let mut succ = HashMap::new();
+    for e in 0..tr.twin.len() {
+        if is_boundary(&alive, e) {
+            succ.insert(tr.origin(e), tr.origin(next_edge(e)));
+        }
+    }
+    let start = *succ.keys().min().unwrap();
+    let mut cycle = vec![start];
+    let mut v = succ[&start];
+    while v != start {
+        cycle.push(v);
+        v = succ[&v];
+    }
+    let m = cycle.len();
+    let pl = (0..m)
+        .filter(|&i| {
+            let (p, q, r) = (cycle[(i + m - 1) % m], cycle[i], cycle[(i + 1) % m]);
+            Point::cross(&points[p], &points[q], &points[r]) != 0
+        })
+        .map(|i| points[cycle[i]].clone())
+        .collect();
+    Ok(SimplePolygon::from_point_list(pl))
+}
+
+#[cfg(test)]
+mod concave_tests {
+    use super::*;
+    #[test]
+    fn test_concave_hull() {
+        //A U shape sampled on a grid, 9 wide and 9 tall, with the columns x = 4 to 6 missing
+        //above y = 2
+        let mut pts = Vec::new();
+        for x in 0..10 {
+            for y in 0..10 {
+                if !(4..=6).contains(&x) || y < 3 {
+                    pts.push(Point::new(x, y));
+                }
+            }
+        }
+        let hull = concave_hull(&pts, ConcaveThreshold::Relative(1.0)).unwrap();
+        assert_eq!(hull.get_point_list().len(), 4);
+        assert_eq!(hull.area().to_f64(), 81.0);
+
+        //Grid edges are 1 long and the diagonals 1.41, so the slot between x = 3 and x = 7 is
+        //carved out, bar a half cell in each of its lower corners
+        let shape = concave_hull(&pts, ConcaveThreshold::Length(1.5)).unwrap();
+        assert_eq!(shape.area().to_f64(), 81.0 - 27.0);
+        assert!(shape.signed_area().to_f64() > 0.0);
+        let dcel = shape.triangulate();
+        assert!(dcel.validate().is_empty());
+        assert_eq!(
+            dcel.get_internal_faces().len(),
+            shape.get_point_list().len() - 2
+        );
+
+        let line: Vec<Point> = (0..5).map(|i| Point::new(i, 2 * i)).collect();
+        assert_eq!(
+            concave_hull(&line, ConcaveThreshold::Length(1.0)).err(),
+            Some(ConcaveHullError::Collinear)
+        );
+        assert_eq!(
+            concave_hull(&pts[..2], ConcaveThreshold::Length(1.0)).err(),
+            Some(ConcaveHullError::TooFewPoints(2))
+        );
+    }
+}
@@ -1,15 +1,19 @@
 // GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
 // Date: April 14, 2022
 // dcel.rs - Implementation of DCEL
-use crate::polygon::SimplePolygon;
+use crate::dual::DualTree;
+use crate::location::{classify, Containment, SlabLocator};
+use crate::polygon::{is_clockwise, SimplePolygon};
 use crate::primitives::{DirEdge, Point};
+use crate::visibility::in_angle;
 use slotmap::{new_key_type, SlotMap};
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
 new_key_type! {
-    struct DCELPointKey;
-    struct DCELEdgeKey;
-    struct DCELFaceKey;
+    pub struct DCELPointKey;
+    pub struct DCELEdgeKey;
+    pub struct DCELFaceKey;
 }
 
 #[derive(Debug)]
@@ -25,6 +29,8 @@ pub struct DCEL {
 #[allow(dead_code)]
 pub struct DCELPoint {
     parent_key: DCELPointKey,
+    //Position in the order the points were added, which for a polygon is its vertex order
+    index: usize,
     point2d: Point,
     incident_edge: Option<DCELEdgeKey>,
 }
@@ -44,8 +50,82 @@ pub struct DCELEdge {
 #[allow(dead_code)]
 pub struct DCELFace {
     parent_key: DCELFaceKey, //TODO - Remove all Options from here
-    inner: Option<DCELEdgeKey>,
+    inner: Vec<DCELEdgeKey>,
     outer: Option<DCELEdgeKey>,
+    isolated: Vec<DCELPointKey>,
+}
+
+impl DCELFace {
+    pub fn get_key(&self) -> DCELFaceKey {
+        self.parent_key
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DCELViolation {
+    //Some link of the half edge is unset or points to a removed element
+    MissingLink(DCELEdgeKey),
+    //prev(next(e)) or next(prev(e)) is not e
+    NextPrevMismatch(DCELEdgeKey),
+    //twin(twin(e)) is not e, or e is its own twin
+    TwinMismatch(DCELEdgeKey),
+    //next(e) does not start where e ends
+    NextOriginMismatch(DCELEdgeKey),
+    //The incident edge of a point does not start at it
+    IncidentEdgeMismatch(DCELPointKey),
+    //Following next from a boundary edge of the face never comes back
+    OpenCycle {
+        face: DCELFaceKey,
+        edge: DCELEdgeKey,
+    },
+    //An edge on a boundary cycle of the face has a different incident face
+    FaceMismatch {
+        face: DCELFaceKey,
+        edge: DCELEdgeKey,
+    },
+    //The edge is not on any boundary cycle listed by a face
+    UnlistedEdge(DCELEdgeKey),
+    //V - E + F is not 2 for the connected component containing point
+    EulerMismatch {
+        point: DCELPointKey,
+        vertices: usize,
+        edges: usize,
+        faces: usize,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DiagonalOutcome {
+    Inserted,
+    AlreadyPresent,
+    //No internal face has both endpoints on its boundary
+    NoCommonFace,
+    //Some endpoint is not a vertex of the DCEL
+    UnknownVertex,
+    CrossesEdge,
+    //The endpoints share a face, but the segment between them runs outside it
+    OutsideFace,
+    //Both endpoints are the same point
+    Degenerate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+impl Colour {
+    pub const ALL: [Colour; 3] = [Colour::Red, Colour::Green, Colour::Blue];
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PlanarGraphError {
+    DegenerateSegment(DirEdge),
+    DuplicateSegment(DirEdge),
+    CrossingSegments(DirEdge, DirEdge),
+    PointOnSegment(Point, DirEdge),
 }
 
 impl DCEL {
@@ -57,218 +137,369 @@ impl DCEL {
     }
 
     fn get_next_edge(&self, e: DCELEdgeKey) -> DCELEdgeKey {
-        let r = self.edges[e].next.unwrap();
-        r
+        self.edges[e].next.unwrap()
     }
 
-    fn get_twin_edge(&self, e: DCELEdgeKey) -> DCELEdgeKey {
+    pub(crate) fn get_twin_edge(&self, e: DCELEdgeKey) -> DCELEdgeKey {
         self.edges[e].twin.unwrap()
     }
 
     fn get_prev_edge(&self, e: DCELEdgeKey) -> DCELEdgeKey {
-        let r = self.edges[e].prev.unwrap();
-        r
+        self.edges[e].prev.unwrap()
     }
 
     fn get_origin_point(&self, e: DCELEdgeKey) -> DCELPointKey {
         self.edges[e].origin.unwrap()
     }
 
-    fn get_common_face(&self, p1: DCELPointKey, p2: DCELPointKey) -> Option<DCELFaceKey> {
+    pub(crate) fn get_edge_points(&self, e: DCELEdgeKey) -> (Point, Point) {
+        let p = &self.points[self.get_origin_point(e)].point2d;
+        let q = &self.points[self.get_origin_point(self.get_twin_edge(e))].point2d;
+        (p.clone(), q.clone())
+    }
+
+    pub(crate) fn get_edge_indices(&self, e: DCELEdgeKey) -> (usize, usize) {
+        let p = self.points[self.get_origin_point(e)].index;
+        let q = self.points[self.get_origin_point(self.get_twin_edge(e))].index;
+        (p, q)
+    }
+
+    pub(crate) fn get_edge_face(&self, e: DCELEdgeKey) -> DCELFaceKey {
+        self.edges[e].incident_face.unwrap()
+    }
+
+    //One half edge per segment, the one starting at the lexicographically smaller endpoint
+    pub(crate) fn get_edge_list(&self) -> Vec<(DCELEdgeKey, Point, Point)> {
+        let mut r = Vec::new();
+        for (e, _) in &self.edges {
+            let (p, q) = self.get_edge_points(e);
+            if (p.x, p.y) < (q.x, q.y) {
+                r.push((e, p, q));
+            }
+        }
+        r
+    }
+
+    //Face containing the point (qx / scale, qy / scale), which must not lie on an edge
+    pub(crate) fn get_face_containing(&self, qx: i128, qy: i128, scale: i128) -> DCELFaceKey {
+        match self.first_edge_left_of(qx, qy, scale) {
+            Some(e) => self.get_edge_face(e),
+            None => self.get_external_face().parent_key,
+        }
+    }
+
+    //Internal faces that have both points on their outer boundary
+    fn get_common_faces(&self, p1: DCELPointKey, p2: DCELPointKey) -> Vec<DCELFaceKey> {
         if p1 == p2 {
             panic!("Why same points?");
         }
+        let mut r = Vec::new();
         for (facekey, face) in &self.faces {
-            let start_edge = face.outer;
-            if start_edge == None {
-                continue;
+            let start_edge = match face.outer {
+                Some(e) => e,
+                None => continue,
+            };
+            let pts = self.get_cycle_pointkey_list(start_edge);
+            if pts.contains(&p1) && pts.contains(&p2) {
+                r.push(facekey);
             }
-            let start_edge = start_edge.unwrap();
-
-            let mut points_found = 0;
-
-            let check_pts = |e| {
-                let e_pt = self.get_origin_point(e);
+        }
+        r
+    }
 
-                if e_pt == p1 || e_pt == p2 {
-                    true
-                } else {
-                    false
-                }
-            };
-            if check_pts(start_edge) {
-                points_found += 1
+    fn find_edge(&self, p1: DCELPointKey, p2: DCELPointKey) -> Option<DCELEdgeKey> {
+        let start = self.points[p1].incident_edge?;
+        let mut curr_edge = start;
+        loop {
+            if self.get_origin_point(self.get_twin_edge(curr_edge)) == p2 {
+                return Some(curr_edge);
             }
-            let mut curr_edge = self.get_next_edge(start_edge);
-            loop {
-                if curr_edge == start_edge {
-                    break;
-                }
-                if check_pts(curr_edge) {
-                    points_found += 1
-                }
-                if points_found == 2 {
-                    return Some(facekey);
-                }
+            curr_edge = self.get_next_edge(self.get_twin_edge(curr_edge));
+            if curr_edge == start {
+                return None;
+            }
+        }
+    }
 
-                curr_edge = self.get_next_edge(curr_edge);
+    //Checks the segment p1 p2 against a face that has both points on its outer boundary. If it
+    //fits, gives the boundary edges leaving p1 and p2 that it splits the corners of, which tells
+    //the visits apart when the boundary passes through a point more than once
+    fn check_diagonal(
+        &self,
+        f: DCELFaceKey,
+        p1: DCELPointKey,
+        p2: DCELPointKey,
+    ) -> Result<(DCELEdgeKey, DCELEdgeKey), DiagonalOutcome> {
+        let face = &self.faces[f];
+        let outer = self.walk_cycle(face.outer.unwrap()).unwrap();
+        let pt = |k: DCELPointKey| &self.points[k].point2d;
+        let (a, b) = (pt(p1), pt(p2));
+
+        //The diagonal has to leave each end into the face, which lies left of its boundary
+        let corner = |p: DCELPointKey, towards: &Point| {
+            outer.iter().copied().find(|&e| {
+                let prev = pt(self.get_origin_point(self.get_prev_edge(e)));
+                let next = pt(self.get_origin_point(self.get_next_edge(e)));
+                self.get_origin_point(e) == p && in_angle(prev, pt(p), next, towards)
+            })
+        };
+        let e1 = corner(p1, b).ok_or(DiagonalOutcome::OutsideFace)?;
+
+        let diagonal = DirEdge::from_points(a, b);
+        let holes = face.inner.iter().map(|&e| self.walk_cycle(e).unwrap());
+        for e in outer.iter().copied().chain(holes.flatten()) {
+            let (u, v) = self.get_edge_points(e);
+            let side = DirEdge::from_points(&u, &v);
+            let touches = if [&u, &v].contains(&a) || [&u, &v].contains(&b) {
+                diagonal.crosses(&side)
+            } else {
+                diagonal.intersects(&side)
+            };
+            if touches {
+                return Err(DiagonalOutcome::CrossesEdge);
             }
         }
-        return None;
+        if face.isolated.iter().any(|&p| diagonal.contains(pt(p))) {
+            return Err(DiagonalOutcome::CrossesEdge);
+        }
+        let e2 = corner(p2, a).ok_or(DiagonalOutcome::OutsideFace)?;
+        Ok((e1, e2))
     }
     fn check_consistency(&self) {
-        for (e, _) in &self.edges {
-            assert_eq!(e, self.get_next_edge(self.get_prev_edge(e)));
-            assert_eq!(e, self.get_prev_edge(self.get_next_edge(e)));
+        let violations = self.validate();
+        if !violations.is_empty() {
+            panic!("Inconsistent DCEL: {:?}", violations);
         }
-        for (f, _) in &self.faces {
-            let e = self.faces[f].outer;
-            if e.is_none() {
-                continue;
-            }
-            let e = e.unwrap();
-            let mut curr_edge = e;
-            loop {
-                assert_eq!(f, self.edges[e].incident_face.unwrap());
-                curr_edge = self.get_next_edge(curr_edge);
-                if curr_edge == e {
-                    break;
-                };
+    }
+
+    //Edges met by following next from start, or None if that never leads back to start
+    fn walk_cycle(&self, start: DCELEdgeKey) -> Option<Vec<DCELEdgeKey>> {
+        let mut r = vec![start];
+        let mut curr_edge = self.get_next_edge(start);
+        while curr_edge != start {
+            if r.len() > self.edges.len() {
+                return None;
             }
+            r.push(curr_edge);
+            curr_edge = self.get_next_edge(curr_edge);
         }
+        Some(r)
     }
-    fn print_edges(&self) {
+
+    //Checks every invariant of the structure and reports each one that does not hold. The cycle
+    //based checks are skipped when some link is missing altogether
+    pub fn validate(&self) -> Vec<DCELViolation> {
+        let mut r = Vec::new();
+        let edge_ok = |e: Option<DCELEdgeKey>| e.is_some_and(|e| self.edges.contains_key(e));
+        for (e, edge) in &self.edges {
+            if !edge_ok(edge.next)
+                || !edge_ok(edge.prev)
+                || !edge_ok(edge.twin)
+                || !edge.origin.is_some_and(|p| self.points.contains_key(p))
+                || !edge
+                    .incident_face
+                    .is_some_and(|f| self.faces.contains_key(f))
+            {
+                r.push(DCELViolation::MissingLink(e));
+            }
+        }
+        for (f, face) in &self.faces {
+            for &e in face.outer.iter().chain(face.inner.iter()) {
+                if !self.edges.contains_key(e) {
+                    r.push(DCELViolation::MissingLink(e));
+                } else if self.edges[e].incident_face != Some(f) {
+                    r.push(DCELViolation::FaceMismatch { face: f, edge: e });
+                }
+            }
+        }
+        if !r.is_empty() {
+            return r;
+        }
+
         for (e, _) in &self.edges {
-            println!(
-                "{:?} {:?} {:?} {:?} {:?}",
-                e,
+            let (next, prev, twin) = (
                 self.get_next_edge(e),
                 self.get_prev_edge(e),
                 self.get_twin_edge(e),
-                self.edges[e].incident_face.unwrap(),
             );
+            if self.edges[next].prev != Some(e) || self.edges[prev].next != Some(e) {
+                r.push(DCELViolation::NextPrevMismatch(e));
+            }
+            if twin == e || self.edges[twin].twin != Some(e) {
+                r.push(DCELViolation::TwinMismatch(e));
+            }
+            if self.get_origin_point(next) != self.get_origin_point(twin) {
+                r.push(DCELViolation::NextOriginMismatch(e));
+            }
         }
-    }
-
-    fn split_with_triangle(&mut self, e_int: DCELEdgeKey) {
-        #[cfg(debug_assertions)]
-        {
-            println!("In triangle split, {:?}", e_int);
-            self.print_edges();
-            self.check_consistency();
+        for (p, point) in &self.points {
+            if let Some(e) = point.incident_edge {
+                if !self.edges.contains_key(e) || self.edges[e].origin != Some(p) {
+                    r.push(DCELViolation::IncidentEdgeMismatch(p));
+                }
+            }
         }
-        let e_prev = e_int;
-        let e_next = self.get_prev_edge(e_int);
-        let e_twin_next = self.get_next_edge(e_int);
-        let e_twin_prev = self.get_prev_edge(e_next);
-
-        let e_orig = self.get_origin_point(e_twin_next);
-        let e_twin_orig = self.get_origin_point(e_next);
-
-        let e_new = self.edges.insert_with_key(|k| DCELEdge {
-            parent_key: k,
-            origin: Some(e_orig),
-            next: Some(e_next),
-            prev: Some(e_prev),
-            twin: None,
-            incident_face: None,
-        });
-        let e_new_twin = self.edges.insert_with_key(|k| DCELEdge {
-            parent_key: k,
-            origin: Some(e_twin_orig),
-            next: Some(e_twin_next),
-            prev: Some(e_twin_prev),
-            twin: None,
-            incident_face: None,
-        });
-
-        self.edges[e_new].twin = Some(e_new_twin);
-        self.edges[e_new_twin].twin = Some(e_new);
-
-        self.edges[e_prev].next = Some(e_new);
-        self.edges[e_next].prev = Some(e_new);
-        self.edges[e_twin_prev].next = Some(e_new_twin);
-        self.edges[e_twin_next].prev = Some(e_new_twin);
-
-        let f = self.faces.insert_with_key(|k| DCELFace {
-            parent_key: k,
-            inner: None,
-            outer: Some(e_new),
-        });
-
-        self.edges[e_new].incident_face = Some(f);
-        self.edges[e_next].incident_face = Some(f);
-        self.edges[e_prev].incident_face = Some(f);
-        let other_face = self.edges[e_twin_next].incident_face.unwrap();
-        self.faces[other_face].outer = Some(e_new_twin);
 
-        self.edges[e_new_twin].incident_face = Some(other_face);
-        #[cfg(debug_assertions)]
-        {
-            println!("After triangle split");
-            self.print_edges();
-            self.check_consistency();
+        let mut listed = HashSet::new();
+        for (f, face) in &self.faces {
+            for &start in face.outer.iter().chain(face.inner.iter()) {
+                let cycle = match self.walk_cycle(start) {
+                    Some(c) => c,
+                    None => {
+                        r.push(DCELViolation::OpenCycle {
+                            face: f,
+                            edge: start,
+                        });
+                        continue;
+                    }
+                };
+                for e in cycle {
+                    if self.edges[e].incident_face != Some(f) {
+                        r.push(DCELViolation::FaceMismatch { face: f, edge: e });
+                    }
+                    listed.insert(e);
+                }
+            }
+        }
+        for (e, _) in &self.edges {
+            if !listed.contains(&e) {
+                r.push(DCELViolation::UnlistedEdge(e));
+            }
         }
-    }
-    //This is terribly inefficient, but I don't care. Because I'm a thug
-    fn split_face(&mut self, p1: DCELPointKey, p2: DCELPointKey) -> bool {
-        #[cfg(debug_assertions)]
-        self.print_edges();
 
-        let common_face = self.get_common_face(p1, p2);
-        if common_face.is_none() {
-            return false;
+        //Euler's formula for every connected component on its own, where the faces of a
+        //component are its boundary cycles. An isolated point has the one face around it
+        let mut parent: HashMap<DCELPointKey, DCELPointKey> = HashMap::new();
+        fn find(parent: &mut HashMap<DCELPointKey, DCELPointKey>, p: DCELPointKey) -> DCELPointKey {
+            let mut root = p;
+            while let Some(&q) = parent.get(&root) {
+                root = q;
+            }
+            let mut curr = p;
+            while curr != root {
+                curr = parent.insert(curr, root).unwrap();
+            }
+            root
         }
-        let start_edge = self.faces[common_face.unwrap()].outer.unwrap();
-        let (e1_next, e2_next) = {
-            let mut curr_edge = start_edge;
-            let (mut e1, mut e2) = (None, None);
-            loop {
-                if self.get_origin_point(curr_edge) == p1 {
-                    e1 = Some(curr_edge);
-                }
-                if self.get_origin_point(curr_edge) == p2 {
-                    e2 = Some(curr_edge);
-                }
-                if e1.is_some() && e2.is_some() {
-                    break (e1.unwrap(), e2.unwrap());
-                }
-                curr_edge = self.get_next_edge(curr_edge);
-                if curr_edge == start_edge {
-                    dbg!(&self, p1, p2);
-                    panic!("This is not supposed to happen");
+        for (e, _) in &self.edges {
+            let a = find(&mut parent, self.get_origin_point(e));
+            let b = find(&mut parent, self.get_origin_point(self.get_twin_edge(e)));
+            if a != b {
+                parent.insert(a, b);
+            }
+        }
+        let mut counts: HashMap<DCELPointKey, (usize, usize, usize)> = HashMap::new();
+        for (p, _) in &self.points {
+            counts.entry(find(&mut parent, p)).or_default().0 += 1;
+        }
+        let mut seen = HashSet::new();
+        for (e, _) in &self.edges {
+            let c = counts
+                .get_mut(&find(&mut parent, self.get_origin_point(e)))
+                .unwrap();
+            c.1 += 1;
+            if seen.contains(&e) {
+                continue;
+            }
+            match self.walk_cycle(e) {
+                Some(cycle) => seen.extend(cycle),
+                None => {
+                    seen.insert(e);
                 }
             }
+            c.2 += 1;
+        }
+        for (point, (vertices, half_edges, cycles)) in counts {
+            let (edges, faces) = (half_edges / 2, cycles.max(1));
+            if vertices + faces != edges + 2 {
+                r.push(DCELViolation::EulerMismatch {
+                    point,
+                    vertices,
+                    edges,
+                    faces,
+                });
+            }
+        }
+        r
+    }
+    //Splits the face by an edge from the origin of e1_next to the origin of e2_next, both on its
+    //outer boundary. Holes and isolated points go to whichever side holds them
+    fn split_face(&mut self, common_face: DCELFaceKey, e1_next: DCELEdgeKey, e2_next: DCELEdgeKey) {
+        let inner = self.faces[common_face].inner.clone();
+        let isolated = self.faces[common_face].isolated.clone();
+        let p1 = self.get_origin_point(e1_next);
+        self.split_outer(common_face, e1_next, e2_next);
+        if !inner.is_empty() || !isolated.is_empty() {
+            let e = self.find_edge(p1, self.get_origin_point(e2_next)).unwrap();
+            self.distribute(e, inner, isolated);
+        }
+    }
+
+    //Hands the holes and isolated points of a face just split by e to the side of e holding them
+    fn distribute(&mut self, e: DCELEdgeKey, inner: Vec<DCELEdgeKey>, isolated: Vec<DCELPointKey>) {
+        let (f1, f2) = (
+            self.get_edge_face(e),
+            self.get_edge_face(self.get_twin_edge(e)),
+        );
+        for f in [f1, f2] {
+            self.faces[f].inner.clear();
+            self.faces[f].isolated.clear();
+        }
+        let ring: Vec<Point> = self
+            .get_cycle_pointkey_list(e)
+            .iter()
+            .map(|&p| self.points[p].point2d.clone())
+            .collect();
+        let side = |f: Option<Containment>| match f {
+            Some(Containment::Inside) => f1,
+            _ => f2,
         };
+        for h in inner {
+            //A hole may touch the boundary, but not everywhere
+            let f = side(
+                self.get_cycle_pointkey_list(h)
+                    .iter()
+                    .map(|&p| classify(&ring, &self.points[p].point2d))
+                    .find(|&c| c != Containment::OnBoundary),
+            );
+            for e in self.walk_cycle(h).unwrap() {
+                self.edges[e].incident_face = Some(f);
+            }
+            self.faces[f].inner.push(h);
+        }
+        for p in isolated {
+            let f = side(Some(classify(&ring, &self.points[p].point2d)));
+            self.faces[f].isolated.push(p);
+        }
+    }
 
+    //The larger side keeps the key of the face and the smaller one gets a new face. Walking both
+    //sides in step finds the smaller one without going round the larger
+    fn split_outer(
+        &mut self,
+        common_face: DCELFaceKey,
+        e1_next: DCELEdgeKey,
+        e2_next: DCELEdgeKey,
+    ) {
+        let (p1, p2) = (
+            self.get_origin_point(e1_next),
+            self.get_origin_point(e2_next),
+        );
         if self.get_next_edge(e1_next) == e2_next || self.get_next_edge(e2_next) == e1_next {
             //This is a triangle
-            return true;
-        }
-
-        if self.get_next_edge(self.get_next_edge(e1_next)) == e2_next {
-            self.split_with_triangle(self.get_next_edge(e1_next));
-            return true;
-        }
-        if self.get_next_edge(self.get_next_edge(e2_next)) == e1_next {
-            self.split_with_triangle(self.get_next_edge(e2_next));
-            return true;
+            return;
         }
 
         let e2_prev = self.get_prev_edge(e1_next);
         let e1_prev = self.get_prev_edge(e2_next);
 
-        //println!("{:?} {:?}", e1_next, e2_next);
-        //println!("{:?} {:?}", e1_prev, e2_prev);
-        //println!("-----");
-
         let e1 = self.edges.insert_with_key(|k| DCELEdge {
             parent_key: k,
             origin: Some(p2),
             next: Some(e1_next),
             prev: Some(e1_prev),
             twin: None,
-            incident_face: None,
+            incident_face: Some(common_face),
         });
         let e2 = self.edges.insert_with_key(|k| DCELEdge {
             parent_key: k,
@@ -276,49 +507,43 @@ impl DCEL {
             next: Some(e2_next),
             prev: Some(e2_prev),
             twin: None,
-            incident_face: None,
+            incident_face: Some(common_face),
         });
 
         self.edges[e1].twin = Some(e2);
         self.edges[e2].twin = Some(e1);
 
-        let f1 = self.faces.insert_with_key(|k| DCELFace {
-            parent_key: k,
-            inner: None,
-            outer: Some(e1),
-        });
-        let f2 = self.faces.insert_with_key(|k| DCELFace {
-            parent_key: k,
-            inner: None,
-            outer: Some(e2),
-        });
-
         self.edges[e1_next].prev = Some(e1);
         self.edges[e2_next].prev = Some(e2);
         self.edges[e1_prev].next = Some(e1);
         self.edges[e2_prev].next = Some(e2);
 
-        let mut update_faces = |e, f| {
-            let mut curr_edge = e;
-            loop {
-                self.edges[curr_edge].incident_face = Some(f);
-                curr_edge = self.get_next_edge(curr_edge);
-                if curr_edge == e {
-                    break;
-                }
+        let (mut c1, mut c2) = (e1_next, e2_next);
+        let (kept, moved) = loop {
+            if c1 == e1 {
+                break (e2, e1);
+            }
+            if c2 == e2 {
+                break (e1, e2);
             }
+            c1 = self.get_next_edge(c1);
+            c2 = self.get_next_edge(c2);
         };
-        update_faces(e1, f1);
-        update_faces(e2, f2);
-
-        self.faces.remove(common_face.unwrap());
-        true
+        let f = self.faces.insert_with_key(|k| DCELFace {
+            parent_key: k,
+            inner: Vec::new(),
+            outer: Some(moved),
+            isolated: Vec::new(),
+        });
+        for e in self.walk_cycle(moved).unwrap() {
+            self.edges[e].incident_face = Some(f);
+        }
+        self.faces[common_face].outer = Some(kept);
     }
 
     pub fn get_external_face(&self) -> &DCELFace {
         for (_, f) in &self.faces {
             if f.outer.is_none() {
-                assert!(f.inner.is_some());
                 return f;
             }
         }
@@ -328,166 +553,136 @@ impl DCEL {
     pub fn get_internal_faces(&self) -> Vec<&DCELFace> {
         let mut r = Vec::new();
         for (_, f) in &self.faces {
-            if f.inner.is_none() {
-                assert!(f.outer.is_some());
+            if f.outer.is_some() {
                 r.push(f)
-            } else {
-                assert!(f.outer.is_none())
             }
         }
         r
     }
 
-    fn construct_adjacent_face_map(&self) -> HashMap<DCELFaceKey, [Option<DCELFaceKey>; 3]> {
-        let faces = &self.get_internal_faces();
-        let external_face = self.get_external_face().parent_key;
-        let mut adjacent_faces = HashMap::new();
-        for f in faces {
-            let start_edge = f.outer.unwrap();
-            assert_eq!(
-                start_edge,
-                self.get_next_edge(self.get_next_edge(self.get_next_edge(start_edge)))
-            );
-            let e1 = self.get_twin_edge(start_edge);
-            let e2 = self.get_twin_edge(self.get_next_edge(start_edge));
-            let e3 = self.get_twin_edge(self.get_prev_edge(start_edge));
-
-            let f1 = self.edges[e1].incident_face.unwrap();
-            let f2 = self.edges[e2].incident_face.unwrap();
-            let f3 = self.edges[e3].incident_face.unwrap();
-            let transform = |x| {
-                if x == external_face {
-                    None
-                } else {
-                    Some(x)
-                }
-            };
-            let (f1, f2, f3) = (transform(f1), transform(f2), transform(f3));
-            adjacent_faces.insert(f.parent_key, [f1, f2, f3]);
-        }
-        adjacent_faces
+    pub fn dual_tree(&self) -> DualTree {
+        DualTree::from_dcel(self)
     }
 
-    pub fn dual_graph(&self) -> Vec<DirEdge> {
-        let adjacent_faces = self.construct_adjacent_face_map();
+    pub fn slab_locator(&self) -> SlabLocator {
+        SlabLocator::from_dcel(self)
+    }
 
-        let get_centroid = |f| {
-            let p = self.get_pointkey_list(f);
-            assert_eq!(p.len(), 3);
-            let mut x_new = 0;
-            let mut y_new = 0;
-            for i in 0..3 {
-                x_new += self.points[p[i]].point2d.x;
-                y_new += self.points[p[i]].point2d.y;
+    //Colours the vertices of a triangulation so that no triangle repeats a colour. The dual tree
+    //is walked breadth first, so every triangle after the first in its component already has
+    //two coloured vertices
+    pub fn three_color(&self) -> HashMap<usize, Colour> {
+        let dual = self.dual_tree();
+        let mut colouring = HashMap::new();
+        let mut seen = vec![false; dual.get_node_count()];
+        for root in 0..dual.get_node_count() {
+            if seen[root] {
+                continue;
             }
-            x_new /= 3;
-            y_new /= 3;
-            Point::new(x_new, y_new)
-        };
-
-        let mut temp_ret = HashSet::new();
-
-        for (fk, _) in &adjacent_faces {
-            let af = &adjacent_faces[&fk];
-            for f in af {
-                if f.is_none() {
-                    continue;
+            for (node, _) in dual.bfs(root) {
+                seen[node] = true;
+                let pts: Vec<usize> = self
+                    .get_pointkey_list(dual.get_face(node))
+                    .iter()
+                    .map(|&p| self.points[p].index)
+                    .collect();
+                if pts.len() != 3 {
+                    panic!("Cannot three color a face that is not a triangle!");
                 }
-                let f = f.unwrap();
-                if !temp_ret.contains(&(f, *fk)) {
-                    temp_ret.insert((*fk, f));
+                let mut free: Vec<Colour> = Colour::ALL
+                    .into_iter()
+                    .filter(|c| !pts.iter().any(|p| colouring.get(p) == Some(c)))
+                    .collect();
+                for p in pts {
+                    colouring.entry(p).or_insert_with(|| free.remove(0));
                 }
             }
         }
-        let mut ret = Vec::new();
-        for (f1, f2) in temp_ret {
-            ret.push(DirEdge::from_points(&get_centroid(f1), &get_centroid(f2)));
-        }
-        ret
+        colouring
     }
 
-    pub fn three_color(&self) -> HashMap<Point, usize> {
-        let faces = &self.get_internal_faces();
-        let adjacent_faces = self.construct_adjacent_face_map();
-        fn recursive_engine(
-            curr_face: DCELFaceKey,
-            parent_face: DCELFaceKey,
-            adjacent_faces: &HashMap<DCELFaceKey, [Option<DCELFaceKey>; 3]>,
-            //adjacent_faces: &HashMap,
-            coloring: &mut HashMap<DCELPointKey, usize>,
-            dcel: &DCEL,
-        ) {
-            #[cfg(debug_assertions)]
-            {
-                println!("---------------------");
-                println!("{:?} {:?} {:?}", curr_face, parent_face, coloring);
-            }
-            let pts = DCEL::get_pointkey_list(dcel, curr_face);
-            let mut forbidden_colors = HashSet::new();
-            let mut new_pts = HashSet::new();
-            for p in pts {
-                if coloring.contains_key(&p) {
-                    forbidden_colors.insert(coloring[&p]);
-                } else {
-                    new_pts.insert(p);
-                }
-            }
-            #[cfg(debug_assertions)]
+    //Internal faces that are not triangles with three distinct colours
+    pub fn verify_colouring(&self, colouring: &HashMap<usize, Colour>) -> Vec<DCELFaceKey> {
+        let mut r = Vec::new();
+        for f in self.get_internal_faces() {
+            let colours: HashSet<Option<&Colour>> = self
+                .get_pointkey_list(f.parent_key)
+                .iter()
+                .map(|&p| colouring.get(&self.points[p].index))
+                .collect();
+            if self.get_pointkey_list(f.parent_key).len() != 3
+                || colours.len() != 3
+                || colours.contains(&None)
             {
-                println!("{:?} {:?}", new_pts, forbidden_colors);
-            }
-            for p in new_pts {
-                if !forbidden_colors.contains(&1) {
-                    coloring.insert(p, 1);
-                    forbidden_colors.insert(1);
-                    continue;
-                };
-                if !forbidden_colors.contains(&2) {
-                    coloring.insert(p, 2);
-                    forbidden_colors.insert(2);
-                    continue;
-                };
-                if !forbidden_colors.contains(&3) {
-                    coloring.insert(p, 3);
-                    forbidden_colors.insert(3);
-                    continue;
-                };
-                panic!("Cannot three color!");
+                r.push(f.parent_key);
             }
+        }
+        r
+    }
 
-            for i in adjacent_faces[&curr_face] {
-                if i.is_none() {
-                    continue;
-                }
-                let f = i.unwrap();
-                if parent_face == f {
-                    continue;
-                }
-                recursive_engine(f, curr_face, adjacent_faces, coloring, dcel);
+    //Points in the order they were added, so that position i holds the vertex with index i
+    pub fn get_vertex_list(&self) -> Vec<Point> {
+        let mut r: Vec<&DCELPoint> = self.points.values().collect();
+        r.sort_by_key(|p| p.index);
+        r.iter().map(|p| p.point2d.clone()).collect()
+    }
+
+    //Outer boundary of an internal face
+    pub(crate) fn get_face_points(&self, f: DCELFaceKey) -> Vec<Point> {
+        self.get_pointkey_list(f)
+            .iter()
+            .map(|&p| self.points[p].point2d.clone())
+            .collect()
+    }
+
+    pub(crate) fn get_face_indices(&self, f: DCELFaceKey) -> Vec<usize> {
+        self.get_pointkey_list(f)
+            .iter()
+            .map(|&p| self.points[p].index)
+            .collect()
+    }
+
+    //Half edges leaving the point, in clockwise order around it. Empty for a point that is not a
+    //vertex
+    pub(crate) fn get_outgoing_edges(&self, p: &Point) -> Vec<DCELEdgeKey> {
+        let mut r = Vec::new();
+        let start = match self
+            .get_dcelpoint_key(p)
+            .and_then(|k| self.points[k].incident_edge)
+        {
+            Some(e) => e,
+            None => return r,
+        };
+        let mut curr_edge = start;
+        loop {
+            r.push(curr_edge);
+            curr_edge = self.get_next_edge(self.get_twin_edge(curr_edge));
+            if curr_edge == start {
+                return r;
             }
         }
-        let mut tempret = HashMap::new();
-        recursive_engine(
-            faces[0].parent_key,
-            faces[0].parent_key,
-            &adjacent_faces,
-            &mut tempret,
-            self,
-        );
-        debug_assert_eq!(self.points.len(), tempret.len());
+    }
 
-        let mut ret = HashMap::new();
-        for (k, v) in tempret {
-            let p2d = self.points[k].point2d.clone();
-            ret.insert(p2d, v);
-        }
-        ret
+    pub(crate) fn get_prev_edge_points(&self, e: DCELEdgeKey) -> (Point, Point) {
+        self.get_edge_points(self.get_prev_edge(e))
+    }
+
+    //Every boundary cycle of a face, the outer one first
+    pub(crate) fn get_face_cycles(&self, f: DCELFaceKey) -> Vec<Vec<DCELEdgeKey>> {
+        let face = &self.faces[f];
+        face.outer
+            .iter()
+            .chain(face.inner.iter())
+            .map(|&e| self.walk_cycle(e).unwrap())
+            .collect()
     }
 
     fn get_pointkey_list(&self, f: DCELFaceKey) -> Vec<DCELPointKey> {
+        self.get_cycle_pointkey_list(self.faces[f].outer.unwrap())
+    }
+
+    fn get_cycle_pointkey_list(&self, start_edge: DCELEdgeKey) -> Vec<DCELPointKey> {
         let mut r = Vec::new();
-        let face = &self.faces[f];
-        let start_edge = face.outer.unwrap();
         {
             let mut curr_edge = start_edge;
             loop {
@@ -508,17 +703,96 @@ impl DCEL {
             .collect()
     }
 
-    pub fn add_internal_diagonals(&mut self, diagonals: &Vec<DirEdge>) {
-        let mut int_diagonals = Vec::new();
+    //Like get_point_list, but giving the index of each point instead
+    pub fn get_index_list(&self, face: &DCELFace) -> Vec<usize> {
+        let x = self.get_pointkey_list(face.parent_key);
+        x.into_iter().map(|x| self.points[x].index).collect()
+    }
+
+    //Boundaries of the holes in a face, each listed with the face on its left
+    pub fn get_inner_point_lists(&self, face: &DCELFace) -> Vec<Vec<Point>> {
+        face.inner
+            .iter()
+            .map(|&e| {
+                self.get_cycle_pointkey_list(e)
+                    .into_iter()
+                    .map(|x| self.points[x].point2d.clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn get_isolated_points(&self, face: &DCELFace) -> Vec<Point> {
+        face.isolated
+            .iter()
+            .map(|&x| self.points[x].point2d.clone())
+            .collect()
+    }
+
+    //Inserts the diagonals one after the other and reports what happened to each of them
+    pub fn add_internal_diagonals(&mut self, diagonals: &Vec<DirEdge>) -> Vec<DiagonalOutcome> {
+        let mut r = Vec::new();
         for e in diagonals {
-            let p1 = self.get_dcelpoint_key(&e.start).unwrap();
-            let p2 = self.get_dcelpoint_key(&e.end).unwrap();
-            int_diagonals.push((p1, p2));
+            let (p1, p2) = match (
+                self.get_dcelpoint_key(&e.start),
+                self.get_dcelpoint_key(&e.end),
+            ) {
+                (Some(p1), Some(p2)) => (p1, p2),
+                _ => {
+                    r.push(DiagonalOutcome::UnknownVertex);
+                    continue;
+                }
+            };
+            if p1 == p2 {
+                r.push(DiagonalOutcome::Degenerate);
+                continue;
+            }
+            if self.find_edge(p1, p2).is_some() {
+                r.push(DiagonalOutcome::AlreadyPresent);
+                continue;
+            }
+            let faces = self.get_common_faces(p1, p2);
+            if faces.is_empty() {
+                r.push(DiagonalOutcome::NoCommonFace);
+                continue;
+            }
+            //A vertex can be on several faces, but the diagonal can only lie inside one of them
+            let mut outcome = DiagonalOutcome::OutsideFace;
+            for f in faces {
+                match self.check_diagonal(f, p1, p2) {
+                    Ok((e1, e2)) => {
+                        self.split_face(f, e1, e2);
+                        outcome = DiagonalOutcome::Inserted;
+                        break;
+                    }
+                    Err(DiagonalOutcome::CrossesEdge) => outcome = DiagonalOutcome::CrossesEdge,
+                    Err(_) => {}
+                }
+            }
+            r.push(outcome);
         }
-        let int_diagonals = int_diagonals;
-        for (p1, p2) in int_diagonals {
-            self.split_face(p1, p2);
+        r
+    }
+
+    //Inserts diagonals known to lie inside faces of the DCEL, such as those of a triangulation
+    //of the polygon it was built from, without checking them against the face. The corner of
+    //each end is found by turning around the vertex, so no face is walked
+    pub(crate) fn add_trusted_diagonals(&mut self, diagonals: &[DirEdge]) {
+        for d in diagonals {
+            let corner = |p: &Point, towards: &Point| {
+                self.get_outgoing_edges(p).into_iter().find(|&e| {
+                    let (prev, _) = self.get_prev_edge_points(e);
+                    let (_, next) = self.get_edge_points(e);
+                    in_angle(&prev, p, &next, towards)
+                })
+            };
+            let e1 = corner(&d.start, &d.end).unwrap();
+            let e2 = corner(&d.end, &d.start).unwrap();
+            debug_assert_eq!(self.get_edge_face(e1), self.get_edge_face(e2));
+            self.split_face(self.get_edge_face(e1), e1, e2);
         }
+        #[cfg(debug_assertions)]
+        self.check_consistency();
     }
 
     pub fn from_simple_polygon(p: &SimplePolygon) -> Self {
@@ -537,20 +811,30 @@ impl DCEL {
         //Creating face placeholders
         let f_inside = ret.faces.insert_with_key(|k| DCELFace {
             parent_key: k,
-            inner: None,
+            inner: Vec::new(),
             outer: None,
+            isolated: Vec::new(),
         });
         let f_outside = ret.faces.insert_with_key(|k| DCELFace {
             parent_key: k,
-            inner: None,
+            inner: Vec::new(),
             outer: None,
+            isolated: Vec::new(),
         });
 
+        //The edges along the point list have the inside on their left unless it runs clockwise
+        let (f_left, f_right) = if is_clockwise(inp_point_list) {
+            (f_outside, f_inside)
+        } else {
+            (f_inside, f_outside)
+        };
+
         //Creating points and edges
-        for idx in 0..inp_size {
-            let cur_pt = &inp_point_list[idx];
+        for cur_pt in inp_point_list {
+            let index = ret.points.len();
             let p = ret.points.insert_with_key(|k| DCELPoint {
                 parent_key: k,
+                index,
                 point2d: cur_pt.clone(),
                 incident_edge: None,
             });
@@ -585,12 +869,12 @@ impl DCEL {
             e.origin = Some(curr_point_key);
             e.next = Some(next_edge_key);
             e.prev = Some(prev_edge_key);
-            e.incident_face = Some(f_inside);
+            e.incident_face = Some(f_left);
         }
 
         let mut twin_edges = Vec::new();
 
-        for twin_idx in 0..inp_size {
+        for (twin_idx, &twin_key) in edge_key_vec.iter().enumerate() {
             let e_key = ret.edges.insert_with_key(|k| DCELEdge {
                 parent_key: k,
                 origin: None,
@@ -609,38 +893,320 @@ impl DCEL {
                 twin_idx + 1
             };
 
-            let twin_key = edge_key_vec[twin_idx];
             let origin_point_key = point_key_vec[origin_idx];
 
             e.origin = Some(origin_point_key);
             e.next = None;
             e.prev = None;
             e.twin = Some(twin_key);
-            e.incident_face = Some(f_outside);
+            e.incident_face = Some(f_right);
 
             ret.edges[twin_key].twin = Some(e_key);
         }
         assert_eq!(twin_edges.len(), inp_size);
 
         for edge_key in &twin_edges {
-            let e = &ret.edges[edge_key.clone()];
+            let e = &ret.edges[*edge_key];
             let e_twin = &ret.edges[e.twin.unwrap()];
             let e_next = ret.edges[e_twin.prev.unwrap()].twin;
             let e_prev = ret.edges[e_twin.next.unwrap()].twin;
-            let e = &mut ret.edges[edge_key.clone()];
+            let e = &mut ret.edges[*edge_key];
             e.next = e_next;
             e.prev = e_prev;
         }
-        ret.faces[f_inside].outer = Some(edge_key_vec[0]);
-        ret.faces[f_outside].inner = Some(twin_edges[0]);
+        let (inside_edge, outside_edge) = if f_left == f_inside {
+            (edge_key_vec[0], twin_edges[0])
+        } else {
+            (twin_edges[0], edge_key_vec[0])
+        };
+        ret.faces[f_inside].outer = Some(inside_edge);
+        ret.faces[f_outside].inner.push(outside_edge);
         ret
     }
+
+    //Half edge seen first when walking left from q = (qx / scale, qy / scale), i.e. the boundary
+    //of the face containing q. Only works once next/twin are set up, and None means q is in the
+    //unbounded face
+    fn first_edge_left_of(&self, qx: i128, qy: i128, scale: i128) -> Option<DCELEdgeKey> {
+        enum Hit {
+            Edge(DCELEdgeKey),
+            Vertex(DCELPointKey),
+        }
+        //Hit abscissa kept as an exact fraction num / den, with den > 0
+        let mut best: Option<(i128, i128, Hit)> = None;
+        let mut consider = |num: i128, den: i128, hit: Hit| {
+            if num * scale >= qx * den {
+                return;
+            }
+            let better = match &best {
+                None => true,
+                Some((b_num, b_den, b_hit)) => {
+                    let (l, r) = (num * b_den, *b_num * den);
+                    l > r
+                        || (l == r
+                            && matches!(hit, Hit::Vertex(_))
+                            && matches!(b_hit, Hit::Edge(_)))
+                }
+            };
+            if better {
+                best = Some((num, den, hit));
+            }
+        };
+        for (e, edge) in &self.edges {
+            let a = &self.points[edge.origin.unwrap()].point2d;
+            let b = &self.points[self.get_origin_point(self.get_twin_edge(e))].point2d;
+            let (ax, ay) = (a.x as i128 * scale, a.y as i128 * scale);
+            let (by, dx, dy) = (
+                b.y as i128 * scale,
+                (b.x - a.x) as i128,
+                (b.y - a.y) as i128,
+            );
+            if ay.min(by) > qy || ay.max(by) < qy {
+                continue;
+            }
+            if ay == qy {
+                consider(ax, scale, Hit::Vertex(edge.origin.unwrap()));
+                continue;
+            }
+            if by == qy {
+                continue;
+            }
+            //Each crossing edge is reported by the one half edge that has q on its left
+            if dx * (qy - ay) - dy * (qx - ax) <= 0 {
+                continue;
+            }
+            let num = ax * dy + (qy - ay) * dx;
+            if dy > 0 {
+                consider(num, scale * dy, Hit::Edge(e));
+            } else {
+                consider(-num, -scale * dy, Hit::Edge(e));
+            }
+        }
+        match best?.2 {
+            Hit::Edge(e) => Some(e),
+            Hit::Vertex(v) => {
+                //q lies in the wedge containing the +x direction, which is bounded clockwise by
+                //the outgoing edge with the largest angle
+                let w = &self.points[v].point2d;
+                let start = self.points[v].incident_edge.unwrap();
+                let mut r = start;
+                let mut curr_edge = start;
+                loop {
+                    let dest =
+                        |e| &self.points[self.get_origin_point(self.get_twin_edge(e))].point2d;
+                    if Point::angular_cmp(w, dest(curr_edge), dest(r)) == Ordering::Greater {
+                        r = curr_edge;
+                    }
+                    curr_edge = self.get_next_edge(self.get_twin_edge(curr_edge));
+                    if curr_edge == start {
+                        break;
+                    }
+                }
+                Some(r)
+            }
+        }
+    }
+
+    //Builds the subdivision induced by a planar straight-line graph. Segments may only meet at
+    //their endpoints, and faces (with their holes and isolated points) are found automatically
+    pub fn from_planar_graph(
+        segments: &[DirEdge],
+        isolated_points: &[Point],
+    ) -> Result<Self, PlanarGraphError> {
+        let mut order: Vec<usize> = (0..segments.len()).collect();
+        order.sort_by_key(|&i| segments[i].start.x.min(segments[i].end.x));
+        for (oi, &i) in order.iter().enumerate() {
+            let s = &segments[i];
+            if s.start == s.end {
+                return Err(PlanarGraphError::DegenerateSegment(s.clone()));
+            }
+            let max_x = s.start.x.max(s.end.x);
+            for &j in &order[oi + 1..] {
+                let t = &segments[j];
+                if t.start.x.min(t.end.x) > max_x {
+                    break;
+                }
+                if (s.start == t.start && s.end == t.end) || (s.start == t.end && s.end == t.start)
+                {
+                    return Err(PlanarGraphError::DuplicateSegment(t.clone()));
+                }
+                if t.start != t.end && s.crosses(t) {
+                    return Err(PlanarGraphError::CrossingSegments(s.clone(), t.clone()));
+                }
+            }
+        }
+        for p in isolated_points {
+            for s in segments {
+                if DirEdge::from_points(p, p).intersects(s) {
+                    return Err(PlanarGraphError::PointOnSegment(p.clone(), s.clone()));
+                }
+            }
+        }
+
+        let mut ret = DCEL {
+            points: SlotMap::with_key(),
+            edges: SlotMap::with_key(),
+            faces: SlotMap::with_key(),
+            point_hash: HashMap::new(),
+        };
+        let f_outside = ret.faces.insert_with_key(|k| DCELFace {
+            parent_key: k,
+            inner: Vec::new(),
+            outer: None,
+            isolated: Vec::new(),
+        });
+
+        let mut outgoing: HashMap<DCELPointKey, Vec<DCELEdgeKey>> = HashMap::new();
+        let get_point = |dcel: &mut DCEL, p: &Point| {
+            if let Some(k) = dcel.get_dcelpoint_key(p) {
+                return k;
+            }
+            let index = dcel.points.len();
+            let k = dcel.points.insert_with_key(|k| DCELPoint {
+                parent_key: k,
+                index,
+                point2d: p.clone(),
+                incident_edge: None,
+            });
+            dcel.point_hash.insert(p.clone(), k);
+            k
+        };
+        for s in segments {
+            let a = get_point(&mut ret, &s.start);
+            let b = get_point(&mut ret, &s.end);
+            let mut new_edge = |origin| {
+                ret.edges.insert_with_key(|k| DCELEdge {
+                    parent_key: k,
+                    origin: Some(origin),
+                    next: None,
+                    prev: None,
+                    twin: None,
+                    incident_face: None,
+                })
+            };
+            let (e1, e2) = (new_edge(a), new_edge(b));
+            ret.edges[e1].twin = Some(e2);
+            ret.edges[e2].twin = Some(e1);
+            outgoing.entry(a).or_default().push(e1);
+            outgoing.entry(b).or_default().push(e2);
+        }
+        let mut isolated_keys = Vec::new();
+        for p in isolated_points {
+            if ret.get_dcelpoint_key(p).is_none() {
+                isolated_keys.push(get_point(&mut ret, p));
+            }
+        }
+
+        //Around every vertex, an incoming edge continues along the next outgoing edge clockwise
+        for (p, mut out) in outgoing {
+            let centre = ret.points[p].point2d.clone();
+            out.sort_by(|&e1, &e2| {
+                let d1 = &ret.points[ret.get_origin_point(ret.get_twin_edge(e1))].point2d;
+                let d2 = &ret.points[ret.get_origin_point(ret.get_twin_edge(e2))].point2d;
+                Point::angular_cmp(&centre, d1, d2)
+            });
+            for i in 0..out.len() {
+                let e_in = ret.get_twin_edge(out[i]);
+                let e_out = out[(i + out.len() - 1) % out.len()];
+                ret.edges[e_in].next = Some(e_out);
+                ret.edges[e_out].prev = Some(e_in);
+            }
+            ret.points[p].incident_edge = Some(out[0]);
+        }
+
+        //Boundary cycles. Counterclockwise ones (positive area) are outer boundaries of bounded
+        //faces, the rest see their component from outside
+        let mut cycle_of = HashMap::new();
+        let mut cycles = Vec::new();
+        for (e, _) in &ret.edges {
+            if cycle_of.contains_key(&e) {
+                continue;
+            }
+            let mut area = 0;
+            let mut leftmost = e;
+            let mut curr_edge = e;
+            loop {
+                cycle_of.insert(curr_edge, cycles.len());
+                let p = &ret.points[ret.get_origin_point(curr_edge)].point2d;
+                let q = &ret.points[ret.get_origin_point(ret.get_next_edge(curr_edge))].point2d;
+                area += p.x as i128 * q.y as i128 - p.y as i128 * q.x as i128;
+                let l = &ret.points[ret.get_origin_point(leftmost)].point2d;
+                if (p.x, p.y) < (l.x, l.y) {
+                    leftmost = curr_edge;
+                }
+                curr_edge = ret.get_next_edge(curr_edge);
+                if curr_edge == e {
+                    break;
+                }
+            }
+            cycles.push((e, area, leftmost));
+        }
+
+        let mut face_of_cycle: Vec<Option<DCELFaceKey>> = vec![None; cycles.len()];
+        for (c, &(e, area, _)) in cycles.iter().enumerate() {
+            if area > 0 {
+                face_of_cycle[c] = Some(ret.faces.insert_with_key(|k| DCELFace {
+                    parent_key: k,
+                    inner: Vec::new(),
+                    outer: Some(e),
+                    isolated: Vec::new(),
+                }));
+            }
+        }
+        //A hole belongs to the face directly to the left of its leftmost vertex. That may be
+        //another hole of the same face, whose leftmost vertex is strictly further left
+        let parent_of_cycle: Vec<Option<usize>> = cycles
+            .iter()
+            .map(|&(_, area, leftmost)| {
+                if area > 0 {
+                    return None;
+                }
+                let q = &ret.points[ret.get_origin_point(leftmost)].point2d;
+                ret.first_edge_left_of(q.x as i128, q.y as i128, 1)
+                    .map(|h| cycle_of[&h])
+            })
+            .collect();
+        let resolve = |mut c: usize| loop {
+            if let Some(f) = face_of_cycle[c] {
+                break f;
+            }
+            match parent_of_cycle[c] {
+                Some(p) => c = p,
+                None => break f_outside,
+            }
+        };
+        for (c, &(e, area, _)) in cycles.iter().enumerate() {
+            let f = resolve(c);
+            if area <= 0 {
+                ret.faces[f].inner.push(e);
+            }
+            let mut curr_edge = e;
+            loop {
+                ret.edges[curr_edge].incident_face = Some(f);
+                curr_edge = ret.get_next_edge(curr_edge);
+                if curr_edge == e {
+                    break;
+                }
+            }
+        }
+        for p in isolated_keys {
+            let q = &ret.points[p].point2d;
+            let f = match ret.first_edge_left_of(q.x as i128, q.y as i128, 1) {
+                Some(h) => resolve(cycle_of[&h]),
+                None => f_outside,
+            };
+            ret.faces[f].isolated.push(p);
+        }
+        Ok(ret)
+    }
 }
 
 #[cfg(test)]
 mod dcel_tests {
     use super::*;
-    use crate::SimplePolygon;
+    use crate::generate;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
     #[test]
     fn test_split() {
         let p = SimplePolygon::gen_rand_hard(5, 1000, 100).unwrap();
@@ -653,9 +1219,13 @@ mod dcel_tests {
         iter.next();
         iter.next();
         let p4 = iter.next().unwrap().0;
-        dbg!(x.get_common_face(p1, p4));
+        let f = x.get_common_faces(p1, p4)[0];
+        dbg!(f);
+        let cycle = x.walk_cycle(x.faces[f].outer.unwrap()).unwrap();
+        let edge_from = |p| *cycle.iter().find(|&&e| x.get_origin_point(e) == p).unwrap();
+        let (e1, e4) = (edge_from(p1), edge_from(p4));
 
-        x.split_face(p1, p4);
+        x.split_face(f, e1, e4);
         for (_, x) in x.faces {
             println!("{:?}", x)
         }
@@ -667,6 +1237,273 @@ mod dcel_tests {
         }
     }
     #[test]
+    fn test_planar_graph() {
+        let e = |x1, y1, x2, y2| DirEdge::from_points(&Point::new(x1, y1), &Point::new(x2, y2));
+        //Two parcels, a pond in the left one and a fence and a well in the right one
+        let segments = vec![
+            e(0, 0, 5, 0),
+            e(5, 0, 10, 0),
+            e(10, 0, 10, 5),
+            e(10, 5, 10, 10),
+            e(10, 10, 5, 10),
+            e(5, 10, 0, 10),
+            e(0, 10, 0, 0),
+            e(5, 0, 5, 10),
+            e(1, 1, 3, 1),
+            e(3, 1, 3, 3),
+            e(3, 3, 1, 3),
+            e(1, 3, 1, 1),
+            e(10, 5, 8, 6),
+            e(7, 5, 8, 5),
+        ];
+        let isolated = vec![Point::new(7, 2), Point::new(20, 20)];
+        let x = DCEL::from_planar_graph(&segments, &isolated).unwrap();
+        assert_eq!(x.validate(), vec![]);
+
+        let ext = x.get_external_face();
+        assert_eq!(x.get_inner_point_lists(ext).len(), 1);
+        assert_eq!(x.get_isolated_points(ext), vec![Point::new(20, 20)]);
+
+        let mut faces: Vec<_> = x
+            .get_internal_faces()
+            .into_iter()
+            .map(|f| {
+                (
+                    x.get_point_list(f).len(),
+                    x.get_inner_point_lists(f)
+                        .iter()
+                        .map(|h| h.len())
+                        .collect::<Vec<_>>(),
+                    x.get_isolated_points(f),
+                )
+            })
+            .collect();
+        faces.sort_by_key(|f| (f.0, f.1.clone()));
+        assert_eq!(
+            faces,
+            vec![
+                (4, vec![], vec![]),
+                (4, vec![4], vec![]),
+                (7, vec![2], vec![Point::new(7, 2)]),
+            ]
+        );
+
+        //The hole is found through a ray that hits a vertex of the diamond
+        let diamond = vec![
+            e(0, 5, 5, 0),
+            e(5, 0, 10, 5),
+            e(10, 5, 5, 10),
+            e(5, 10, 0, 5),
+            e(4, 5, 6, 4),
+            e(6, 4, 6, 6),
+            e(6, 6, 4, 5),
+        ];
+        let x = DCEL::from_planar_graph(&diamond, &[]).unwrap();
+        assert_eq!(x.get_inner_point_lists(x.get_external_face()).len(), 1);
+        let holes: Vec<_> = x
+            .get_internal_faces()
+            .into_iter()
+            .map(|f| x.get_inner_point_lists(f).len())
+            .collect();
+        assert_eq!(holes.iter().sum::<usize>(), 1);
+
+        let crossing = vec![e(0, 0, 10, 10), e(0, 10, 10, 0)];
+        assert!(matches!(
+            DCEL::from_planar_graph(&crossing, &[]),
+            Err(PlanarGraphError::CrossingSegments(_, _))
+        ));
+        assert!(matches!(
+            DCEL::from_planar_graph(&segments, &[Point::new(5, 5)]),
+            Err(PlanarGraphError::PointOnSegment(_, _))
+        ));
+    }
+    #[test]
+    fn test_validate() {
+        let pl = vec![
+            Point::new(0, 0),
+            Point::new(10, 0),
+            Point::new(10, 10),
+            Point::new(0, 10),
+        ];
+        let mut x = DCEL::from_simple_polygon(&SimplePolygon::from_point_list(pl));
+        assert_eq!(x.validate(), vec![]);
+        x.add_internal_diagonals(&vec![DirEdge::from_points(
+            &Point::new(0, 0),
+            &Point::new(10, 10),
+        )]);
+        assert_eq!(x.validate(), vec![]);
+
+        //Reroute the cycles through (10, 0) so that both triangles and the outside become one
+        let key = |p| x.get_dcelpoint_key(&p).unwrap();
+        let (a, b) = (key(Point::new(0, 0)), key(Point::new(10, 0)));
+        let (ab, ba) = {
+            let ab = x
+                .edges
+                .iter()
+                .find(|(e, _)| {
+                    x.get_origin_point(*e) == a && x.get_origin_point(x.get_twin_edge(*e)) == b
+                })
+                .unwrap()
+                .0;
+            (ab, x.get_twin_edge(ab))
+        };
+        let (ab_next, cb) = (x.get_next_edge(ab), x.get_prev_edge(ba));
+        x.edges[ab].next = Some(ba);
+        x.edges[ba].prev = Some(ab);
+        x.edges[cb].next = Some(ab_next);
+        x.edges[ab_next].prev = Some(cb);
+        let v = x.validate();
+        assert!(v
+            .iter()
+            .any(|v| matches!(v, DCELViolation::EulerMismatch { faces: 2, .. })));
+        assert!(v
+            .iter()
+            .any(|v| matches!(v, DCELViolation::FaceMismatch { .. })));
+
+        x.edges[ab].twin = Some(ab);
+        assert!(x.validate().contains(&DCELViolation::TwinMismatch(ab)));
+    }
+    #[test]
+    fn test_diagonal_outcomes() {
+        let pl = [(0, 0), (10, 0), (10, 10), (5, 2), (0, 10)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        let mut x = DCEL::from_simple_polygon(&SimplePolygon::from_point_list(pl));
+        let e = |x1, y1, x2, y2| DirEdge::from_points(&Point::new(x1, y1), &Point::new(x2, y2));
+        let outcomes = x.add_internal_diagonals(&vec![
+            e(0, 0, 10, 10),
+            e(10, 10, 0, 10),
+            e(0, 0, 5, 2),
+            e(10, 0, 0, 0),
+            e(0, 0, 1, 1),
+            e(5, 2, 0, 0),
+            e(10, 10, 0, 10),
+        ]);
+        assert_eq!(
+            outcomes,
+            vec![
+                DiagonalOutcome::CrossesEdge,
+                DiagonalOutcome::OutsideFace,
+                DiagonalOutcome::Inserted,
+                DiagonalOutcome::AlreadyPresent,
+                DiagonalOutcome::UnknownVertex,
+                DiagonalOutcome::AlreadyPresent,
+                DiagonalOutcome::NoCommonFace,
+            ]
+        );
+        assert_eq!(x.get_internal_faces().len(), 2);
+        assert_eq!(x.validate(), vec![]);
+    }
+    #[test]
+    fn test_diagonals_around_holes() {
+        let e = |x1, y1, x2, y2| DirEdge::from_points(&Point::new(x1, y1), &Point::new(x2, y2));
+        //A square with a square hole in the middle and a well at (2, 6)
+        let segments = vec![
+            e(0, 0, 10, 0),
+            e(10, 0, 10, 10),
+            e(10, 10, 0, 10),
+            e(0, 10, 0, 5),
+            e(0, 5, 0, 0),
+            e(4, 4, 6, 4),
+            e(6, 4, 6, 6),
+            e(6, 6, 4, 6),
+            e(4, 6, 4, 4),
+        ];
+        let mut x = DCEL::from_planar_graph(&segments, &[Point::new(2, 6)]).unwrap();
+        let outcomes = x.add_internal_diagonals(&vec![
+            e(0, 0, 10, 10),
+            e(0, 5, 10, 10),
+            e(0, 5, 10, 0),
+            e(10, 0, 0, 10),
+            e(0, 5, 10, 10),
+        ]);
+        //Once the diagonal under the hole is in, the hole and the well are above it
+        assert_eq!(
+            outcomes,
+            vec![
+                DiagonalOutcome::CrossesEdge,
+                DiagonalOutcome::CrossesEdge,
+                DiagonalOutcome::Inserted,
+                DiagonalOutcome::CrossesEdge,
+                DiagonalOutcome::CrossesEdge,
+            ]
+        );
+        assert_eq!(x.validate(), vec![]);
+        let mut faces: Vec<_> = x
+            .get_internal_faces()
+            .into_iter()
+            .map(|f| {
+                (
+                    x.get_point_list(f).len(),
+                    x.get_inner_point_lists(f).len(),
+                    x.get_isolated_points(f),
+                )
+            })
+            .collect();
+        faces.sort_by_key(|f| (f.0, f.1));
+        assert_eq!(
+            faces,
+            vec![
+                (3, 0, vec![]),
+                (4, 0, vec![]),
+                (4, 1, vec![Point::new(2, 6)])
+            ]
+        );
+
+        //A fence from the corner makes the boundary pass (0, 0) twice, and only the visit
+        //between the fence and the bottom edge has room for a diagonal to (10, 10)
+        let mut fenced = segments[..5].to_vec();
+        fenced.push(e(0, 0, 2, 4));
+        let mut x = DCEL::from_planar_graph(&fenced, &[]).unwrap();
+        let outcomes = x.add_internal_diagonals(&vec![e(0, 0, 10, 10), e(2, 4, 0, 10)]);
+        assert_eq!(
+            outcomes,
+            vec![DiagonalOutcome::Inserted, DiagonalOutcome::Inserted]
+        );
+        assert_eq!(x.validate(), vec![]);
+        let mut sizes: Vec<_> = x
+            .get_internal_faces()
+            .into_iter()
+            .map(|f| x.get_point_list(f).len())
+            .collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![3, 4, 4]);
+    }
+    #[test]
+    fn test_three_color() {
+        let pl = [(0, 0), (6, 0), (6, 3), (6, 6), (3, 6), (0, 6)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        let mut x = DCEL::from_simple_polygon(&SimplePolygon::from_point_list(pl));
+        let e = |x2, y2| DirEdge::from_points(&Point::new(0, 0), &Point::new(x2, y2));
+        x.add_internal_diagonals(&vec![e(6, 3), e(6, 6), e(3, 6)]);
+
+        let mut c = x.three_color();
+        assert_eq!(c.len(), 6);
+        assert_eq!(x.verify_colouring(&c), vec![]);
+        //Vertex 0 is on every triangle, so nothing else can share its colour
+        assert!((1..6).all(|i| c[&i] != c[&0]));
+
+        c.insert(2, c[&1]);
+        assert_eq!(x.verify_colouring(&c).len(), 2);
+        c.remove(&0);
+        assert_eq!(x.verify_colouring(&c).len(), 4);
+    }
+    #[test]
+    fn test_three_color_long_spiral() {
+        //A spiral's dual tree is one long path, as deep as the polygon has triangles
+        let mut rng = StdRng::seed_from_u64(31);
+        let n = 50_000;
+        let p = generate::spiral(n, 1_000_000, &mut rng).unwrap();
+        let x = p.triangulate();
+        assert_eq!(x.get_internal_faces().len(), n - 2);
+        let c = x.three_color();
+        assert_eq!(c.len(), n);
+        assert_eq!(x.verify_colouring(&c), vec![]);
+    }
+    #[test]
     fn test_construction() {
         let p = SimplePolygon::gen_rand_hard(5, 1000, 100).unwrap();
         let x = DCEL::from_simple_polygon(&p);
@@ -1,8 +1,8 @@
 // GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
 // Date: April 14, 2022
 // draw.rs - Functions controlling the GUI
-use crate::primitives::Point;
 use piston_window::*;
+use polygon_triangulation::primitives::Point;
 use std::sync::mpsc;
 
 pub type Color = [f32; 4];
@@ -85,12 +85,7 @@ pub fn drawer(rx: mpsc::Receiver<DrawMessage>) {
                             line(
                                 grid_color,
                                 0.5,
-                                [
-                                    i as f64 * x_gran,
-                                    Y_MAX - (0 as f64),
-                                    i as f64 * x_gran,
-                                    Y_MAX - (Y_MAX as f64),
-                                ],
+                                [i as f64 * x_gran, Y_MAX, i as f64 * x_gran, 0.],
                                 c.transform,
                                 g,
                             );
@@ -0,0 +1,245 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// dual.rs - Dual graph of the internal faces of a DCEL
+use crate::dcel::{DCELEdgeKey, DCELFaceKey, DCEL};
+use crate::primitives::Rational;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DualEdge {
+    pub nodes: (usize, usize),
+    //Half-edge of the shared boundary that lies on the face of nodes.0. Only meaningful while
+    //the DCEL it was built from is left unchanged
+    pub half_edge: DCELEdgeKey,
+}
+
+//Nodes are the internal faces, and there is one edge for every boundary segment two internal
+//faces share. For a triangulated simple polygon this is a tree
+#[derive(Debug)]
+pub struct DualTree {
+    faces: Vec<DCELFaceKey>,
+    index: HashMap<DCELFaceKey, usize>,
+    edges: Vec<DualEdge>,
+    adjacency: Vec<Vec<usize>>,
+    centroids: Vec<(Rational, Rational)>,
+}
+
+impl DualTree {
+    pub fn from_dcel(dcel: &DCEL) -> Self {
+        let faces: Vec<DCELFaceKey> = dcel
+            .get_internal_faces()
+            .iter()
+            .map(|f| f.get_key())
+            .collect();
+        let index: HashMap<DCELFaceKey, usize> =
+            faces.iter().enumerate().map(|(i, &f)| (f, i)).collect();
+
+        let mut edges = Vec::new();
+        let mut adjacency = vec![Vec::new(); faces.len()];
+        let mut centroids = Vec::new();
+        for (i, &f) in faces.iter().enumerate() {
+            for cycle in dcel.get_face_cycles(f) {
+                for &e in &cycle {
+                    let other = dcel.get_edge_face(dcel.get_twin_edge(e));
+                    //Every shared segment is seen from both sides, so keep the lower-index one
+                    match index.get(&other) {
+                        Some(&j) if i < j => {
+                            adjacency[i].push(edges.len());
+                            adjacency[j].push(edges.len());
+                            edges.push(DualEdge {
+                                nodes: (i, j),
+                                half_edge: e,
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            centroids.push(area_centroid(dcel, f));
+        }
+        Self {
+            faces,
+            index,
+            edges,
+            adjacency,
+            centroids,
+        }
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    pub fn get_face(&self, node: usize) -> DCELFaceKey {
+        self.faces[node]
+    }
+
+    pub fn get_node(&self, face: DCELFaceKey) -> Option<usize> {
+        self.index.get(&face).copied()
+    }
+
+    pub fn get_edges(&self) -> &[DualEdge] {
+        &self.edges
+    }
+
+    //Neighbouring nodes, each with the index of the edge leading to it
+    pub fn get_neighbours(&self, node: usize) -> Vec<(usize, usize)> {
+        self.adjacency[node]
+            .iter()
+            .map(|&e| {
+                let (a, b) = self.edges[e].nodes;
+                (if a == node { b } else { a }, e)
+            })
+            .collect()
+    }
+
+    pub fn get_degree(&self, node: usize) -> usize {
+        self.adjacency[node].len()
+    }
+
+    //Nodes reachable from root in depth first preorder, each with the edge it was reached by
+    pub fn dfs(&self, root: usize) -> Vec<(usize, Option<usize>)> {
+        let mut visited = vec![false; self.faces.len()];
+        let mut stack = vec![(root, None)];
+        let mut r = Vec::new();
+        while let Some((node, via)) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            r.push((node, via));
+            //Reversed so that neighbours are visited in adjacency order
+            for (n, e) in self.get_neighbours(node).into_iter().rev() {
+                if !visited[n] {
+                    stack.push((n, Some(e)));
+                }
+            }
+        }
+        r
+    }
+
+    //Nodes reachable from root in breadth first order, each with the edge it was reached by
+    pub fn bfs(&self, root: usize) -> Vec<(usize, Option<usize>)> {
+        let mut visited = vec![false; self.faces.len()];
+        visited[root] = true;
+        let mut queue = VecDeque::from([(root, None)]);
+        let mut r = Vec::new();
+        while let Some((node, via)) = queue.pop_front() {
+            r.push((node, via));
+            for (n, e) in self.get_neighbours(node) {
+                if !visited[n] {
+                    visited[n] = true;
+                    queue.push_back((n, Some(e)));
+                }
+            }
+        }
+        r
+    }
+
+    //Nodes of degree at most one. In a triangulated polygon these are the ears
+    pub fn get_leaves(&self) -> Vec<usize> {
+        (0..self.faces.len())
+            .filter(|&n| self.get_degree(n) <= 1)
+            .collect()
+    }
+
+    //Longest path of the tree as a node sequence, found with two breadth first searches
+    pub fn diameter(&self) -> Vec<usize> {
+        if self.faces.is_empty() {
+            return Vec::new();
+        }
+        let farthest = |root: usize| {
+            let mut parent = vec![None; self.faces.len()];
+            let order = self.bfs(root);
+            for &(node, via) in &order {
+                if let Some(e) = via {
+                    let (a, b) = self.edges[e].nodes;
+                    parent[node] = Some(if a == node { b } else { a });
+                }
+            }
+            (order.last().unwrap().0, parent)
+        };
+        let (start, _) = farthest(0);
+        let (end, parent) = farthest(start);
+        let mut path = vec![end];
+        while let Some(p) = parent[*path.last().unwrap()] {
+            path.push(p);
+        }
+        path
+    }
+
+    //Area centroid of the node's face, ignoring holes
+    pub fn get_centroid(&self, node: usize) -> (Rational, Rational) {
+        self.centroids[node]
+    }
+
+    pub fn get_float_centroid(&self, node: usize) -> (f64, f64) {
+        let (x, y) = self.centroids[node];
+        (x.to_f64(), y.to_f64())
+    }
+}
+
+fn area_centroid(dcel: &DCEL, f: DCELFaceKey) -> (Rational, Rational) {
+    let pl = dcel.get_face_points(f);
+    let (mut a2, mut cx, mut cy) = (0i128, 0i128, 0i128);
+    for (i, p) in pl.iter().enumerate() {
+        let q = &pl[(i + 1) % pl.len()];
+        let cross = p.x as i128 * q.y as i128 - q.x as i128 * p.y as i128;
+        a2 += cross;
+        cx += (p.x + q.x) as i128 * cross;
+        cy += (p.y + q.y) as i128 * cross;
+    }
+    (Rational::new(cx, 3 * a2), Rational::new(cy, 3 * a2))
+}
+
+#[cfg(test)]
+mod dual_tests {
+    use super::*;
+    use crate::polygon::SimplePolygon;
+    use crate::primitives::{DirEdge, Point};
+    #[test]
+    fn test_dual_tree() {
+        //A fan of four triangles around (0, 0)
+        let pl = [(0, 0), (6, 0), (6, 3), (6, 6), (3, 6), (0, 6)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        let mut x = DCEL::from_simple_polygon(&SimplePolygon::from_point_list(pl));
+        let e = |x2, y2| DirEdge::from_points(&Point::new(0, 0), &Point::new(x2, y2));
+        x.add_internal_diagonals(&vec![e(6, 3), e(6, 6), e(3, 6)]);
+
+        let t = DualTree::from_dcel(&x);
+        assert_eq!(t.get_node_count(), 4);
+        assert_eq!(t.get_edges().len(), 3);
+        assert_eq!(t.get_leaves().len(), 2);
+        assert_eq!(t.diameter().len(), 4);
+        assert_eq!(t.dfs(0).len(), 4);
+        assert_eq!(t.bfs(t.get_leaves()[0]).len(), 4);
+
+        for de in t.get_edges() {
+            let (a, b) = de.nodes;
+            assert_eq!(x.get_edge_face(de.half_edge), t.get_face(a));
+            assert_eq!(
+                x.get_edge_face(x.get_twin_edge(de.half_edge)),
+                t.get_face(b)
+            );
+            let (u, v) = x.get_edge_points(de.half_edge);
+            assert!(u == Point::new(0, 0) || v == Point::new(0, 0));
+        }
+
+        //The triangle (0, 0) (6, 0) (6, 3) has its centroid at (4, 1)
+        let bottom = x.get_face_containing(5, 1, 1);
+        let (cx, cy) = t.get_centroid(t.get_node(bottom).unwrap());
+        assert_eq!((cx, cy), (Rational::from_int(4), Rational::from_int(1)));
+    }
+    #[test]
+    fn test_centroid_large_coordinates() {
+        //Products of these coordinates overflow isize, but not the i128 sums
+        let m = 1 << 40;
+        let pl = vec![Point::new(0, 0), Point::new(3 * m, 0), Point::new(0, 3 * m)];
+        let x = DCEL::from_simple_polygon(&SimplePolygon::from_point_list(pl));
+        let t = DualTree::from_dcel(&x);
+        let (cx, cy) = t.get_centroid(0);
+        assert_eq!((cx, cy), (Rational::from_int(m), Rational::from_int(m)));
+    }
+}
@@ -0,0 +1,187 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// exterior.rs - Triangulating the region between a polygon and a bound around it
+use crate::dcel::{DCELFace, DCELFaceKey, DCEL};
+use crate::hull::hull_boundary;
+use crate::polygon::SimplePolygon;
+use crate::primitives::{DirEdge, Point};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExteriorBound {
+    //Axis parallel rectangle given by its lower left and upper right corners
+    Rectangle(Point, Point),
+    ConvexHull,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExteriorError {
+    //The rectangle has to hold the polygon strictly inside it
+    BoundTooSmall,
+}
+
+//Triangulation of the polygon and of the region between it and its bound, in one DCEL
+pub struct ExteriorMesh {
+    dcel: DCEL,
+    inside: HashSet<DCELFaceKey>,
+}
+
+impl ExteriorMesh {
+    //Vertices of the polygon come first in the DCEL, with the same indices
+    pub fn get_dcel(&self) -> &DCEL {
+        &self.dcel
+    }
+
+    pub fn is_inside(&self, f: DCELFaceKey) -> bool {
+        self.inside.contains(&f)
+    }
+
+    pub fn get_inside_faces(&self) -> Vec<&DCELFace> {
+        let mut r = self.dcel.get_internal_faces();
+        r.retain(|f| self.is_inside(f.get_key()));
+        r
+    }
+
+    pub fn get_outside_faces(&self) -> Vec<&DCELFace> {
+        let mut r = self.dcel.get_internal_faces();
+        r.retain(|f| !self.is_inside(f.get_key()));
+        r
+    }
+}
+
+impl SimplePolygon {
+    //Triangulates the polygon together with the region out to the bound. Each pocket is
+    //triangulated on its own, and a rectangle is joined to the hull at its leftmost and rightmost
+    //vertices, which splits the ring between them into two simple polygons
+    pub fn triangulate_exterior(
+        &self,
+        bound: &ExteriorBound,
+    ) -> Result<ExteriorMesh, ExteriorError> {
+        let pl = self.get_point_list();
+        let mut pieces = self.pockets();
+        if let ExteriorBound::Rectangle(lo, hi) = bound {
+            if pl
+                .iter()
+                .any(|p| p.x <= lo.x || p.x >= hi.x || p.y <= lo.y || p.y >= hi.y)
+            {
+                return Err(ExteriorError::BoundTooSmall);
+            }
+            let (bl, br) = (lo.clone(), Point::new(hi.x, lo.y));
+            let (tr, tl) = (hi.clone(), Point::new(lo.x, hi.y));
+            let hull: Vec<Point> = hull_boundary(pl).iter().map(|&i| pl[i].clone()).collect();
+            let m = hull.len();
+            let left = (0..m).min_by_key(|&i| (hull[i].x, hull[i].y)).unwrap();
+            let right = (0..m).max_by_key(|&i| (hull[i].x, hull[i].y)).unwrap();
+            //Walks the hull clockwise from one index to the other
+            let chain = |from: usize, to: usize| {
+                let mut r = vec![hull[from].clone()];
+                let mut i = from;
+                while i != to {
+                    i = (i + m - 1) % m;
+                    r.push(hull[i].clone());
+                }
+                r
+            };
+            let mut upper = vec![bl.clone()];
+            upper.append(&mut chain(left, right));
+            upper.extend([tr.clone(), tl]);
+            let mut lower = vec![bl, br, tr];
+            lower.append(&mut chain(right, left));
+            pieces.push(SimplePolygon::from_point_list(upper));
+            pieces.push(SimplePolygon::from_point_list(lower));
+        }
+
+        //The polygon edges go in first and in order, so that its vertices keep their indices
+        let inner = self.triangulate();
+        let mut segments: Vec<DirEdge> = (0..pl.len())
+            .map(|i| DirEdge::from_points(&pl[i], &pl[(i + 1) % pl.len()]))
+            .collect();
+        let mut seen: HashSet<(Point, Point)> = HashSet::new();
+        for s in &segments {
+            seen.insert((s.start.clone(), s.end.clone()));
+            seen.insert((s.end.clone(), s.start.clone()));
+        }
+        let triangulations: Vec<DCEL> = pieces.iter().map(|p| p.triangulate()).collect();
+        for dcel in std::iter::once(&inner).chain(triangulations.iter()) {
+            for (_, a, b) in dcel.get_edge_list() {
+                if seen.insert((a.clone(), b.clone())) {
+                    seen.insert((b.clone(), a.clone()));
+                    segments.push(DirEdge::from_points(&a, &b));
+                }
+            }
+        }
+        let dcel = DCEL::from_planar_graph(&segments, &[]).expect("Pieces overlap");
+
+        //Every triangle of the polygon holds its centroid, which picks out the same face here
+        let inside = inner
+            .get_internal_faces()
+            .iter()
+            .map(|f| {
+                let t = inner.get_point_list(f);
+                let sx: i128 = t.iter().map(|p| p.x as i128).sum();
+                let sy: i128 = t.iter().map(|p| p.y as i128).sum();
+                dcel.get_face_containing(sx, sy, 3)
+            })
+            .collect();
+        Ok(ExteriorMesh { dcel, inside })
+    }
+}
+
+#[cfg(test)]
+mod exterior_tests {
+    use super::*;
+    use crate::fixtures::{comb, notched_square};
+    #[test]
+    fn test_triangulate_exterior() {
+        let p = notched_square();
+        let pl = p.get_point_list().clone();
+
+        let mesh = p.triangulate_exterior(&ExteriorBound::ConvexHull).unwrap();
+        let dcel = mesh.get_dcel();
+        assert!(dcel.validate().is_empty());
+        assert_eq!(dcel.get_vertex_list()[..8], pl[..]);
+        //n - 2 triangles inside and two filling the notch
+        assert_eq!(mesh.get_inside_faces().len(), 6);
+        assert_eq!(mesh.get_outside_faces().len(), 2);
+
+        let bound = ExteriorBound::Rectangle(Point::new(-2, -2), Point::new(12, 12));
+        let mesh = p.triangulate_exterior(&bound).unwrap();
+        let dcel = mesh.get_dcel();
+        assert!(dcel.validate().is_empty());
+        assert_eq!(mesh.get_inside_faces().len(), 6);
+        //A ring has as many triangles as vertices, six on the hull and four corners
+        assert_eq!(mesh.get_outside_faces().len(), 12);
+        for f in dcel.get_internal_faces() {
+            assert_eq!(dcel.get_point_list(f).len(), 3);
+        }
+
+        let small = ExteriorBound::Rectangle(Point::new(0, -2), Point::new(12, 12));
+        assert_eq!(
+            p.triangulate_exterior(&small).err(),
+            Some(ExteriorError::BoundTooSmall)
+        );
+    }
+    #[test]
+    fn test_triangulate_exterior_comb() {
+        //Three gaps between the teeth, each a single triangle under its lid
+        let p = comb();
+        let mesh = p.triangulate_exterior(&ExteriorBound::ConvexHull).unwrap();
+        assert!(mesh.get_dcel().validate().is_empty());
+        assert_eq!(mesh.get_inside_faces().len(), 8);
+        assert_eq!(mesh.get_outside_faces().len(), 3);
+
+        //Seven vertices on the hull, three of them along its top, and four corners
+        let bound = ExteriorBound::Rectangle(Point::new(-1, -1), Point::new(13, 7));
+        let mesh = p.triangulate_exterior(&bound).unwrap();
+        assert!(mesh.get_dcel().validate().is_empty());
+        assert_eq!(mesh.get_outside_faces().len(), 3 + 11);
+
+        let pl: Vec<Point> = p.get_point_list().iter().rev().cloned().collect();
+        let cw = SimplePolygon::from_point_list(pl.clone());
+        let mesh = cw.triangulate_exterior(&bound).unwrap();
+        assert!(mesh.get_dcel().validate().is_empty());
+        assert_eq!(mesh.get_dcel().get_vertex_list()[..10], pl[..]);
+        assert_eq!(mesh.get_inside_faces().len(), 8);
+        assert_eq!(mesh.get_outside_faces().len(), 3 + 11);
+    }
+}
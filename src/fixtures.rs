@@ -0,0 +1,41 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// fixtures.rs - Polygons shared by the tests of several modules
+use crate::polygon::SimplePolygon;
+use crate::primitives::Point;
+
+pub(crate) fn points(v: &[(isize, isize)]) -> Vec<Point> {
+    v.iter().map(|&(x, y)| Point::new(x, y)).collect()
+}
+
+//A 10 by 10 square with a notch 2 wide cut down from the top to y = 4, listed counterclockwise
+//from the origin. The two arms either side of the notch hide parts of each other
+pub(crate) fn notched_square() -> SimplePolygon {
+    SimplePolygon::from_point_list(points(&[
+        (0, 0),
+        (10, 0),
+        (10, 10),
+        (6, 10),
+        (6, 4),
+        (4, 4),
+        (4, 10),
+        (0, 10),
+    ]))
+}
+
+//A 12 by 6 comb hanging its teeth down to y = 1 at x = 2, 6 and 10. A line along y = 1 grazes
+//every tooth tip, and no one vertex sees into all the gaps between the teeth
+pub(crate) fn comb() -> SimplePolygon {
+    SimplePolygon::from_point_list(points(&[
+        (0, 0),
+        (12, 0),
+        (12, 6),
+        (11, 6),
+        (10, 1),
+        (8, 6),
+        (6, 1),
+        (4, 6),
+        (2, 1),
+        (0, 6),
+    ]))
+}
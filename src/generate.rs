@@ -0,0 +1,579 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// generate.rs - Random polygons from several shape families
+use crate::polygon::{self, PolygonStats, SimplePolygon};
+use crate::primitives::{gcd, Point, PointOrientation};
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+//Attempts made before a randomised generator gives up
+const RETRIES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonFamily {
+    Convex,
+    Star,
+    Orthogonal,
+    Spiral,
+    Comb,
+    SpacePartition,
+    TwoOpt,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GenerateError {
+    //The family has no polygon with this many vertices, such as an orthogonal one with an odd count
+    UnsupportedVertexCount(usize),
+    //The shape does not fit in the coordinate range
+    CoordinatesTooSmall,
+    RetriesExhausted,
+}
+
+//Polygon of the family with n vertices, all in [0, max_coord) on both axes and listed
+//counterclockwise, along with its vertex statistics
+pub fn generate<R: Rng + ?Sized>(
+    family: PolygonFamily,
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<(SimplePolygon, PolygonStats), GenerateError> {
+    let p = match family {
+        PolygonFamily::Convex => convex(n, max_coord, rng),
+        PolygonFamily::Star => star(n, max_coord, rng),
+        PolygonFamily::Orthogonal => orthogonal(n, max_coord, rng),
+        PolygonFamily::Spiral => spiral(n, max_coord, rng),
+        PolygonFamily::Comb => comb(n, max_coord, rng),
+        PolygonFamily::SpacePartition => space_partition(n, max_coord, rng),
+        PolygonFamily::TwoOpt => two_opt(n, max_coord, rng),
+    }?;
+    let stats = p.get_stats();
+    Ok((p, stats))
+}
+
+//Like generate, drawing from a generator seeded with seed so that the polygon can be rebuilt
+pub fn generate_seeded(
+    family: PolygonFamily,
+    n: usize,
+    max_coord: usize,
+    seed: u64,
+) -> Result<(SimplePolygon, PolygonStats), GenerateError> {
+    generate(family, n, max_coord, &mut StdRng::seed_from_u64(seed))
+}
+
+//Moves the points so that the smallest coordinates are 0, failing if they then reach max_coord
+fn fit(mut pl: Vec<Point>, max_coord: usize) -> Result<Vec<Point>, GenerateError> {
+    let (x0, y0) = (
+        pl.iter().map(|p| p.x).min().unwrap(),
+        pl.iter().map(|p| p.y).min().unwrap(),
+    );
+    for p in &mut pl {
+        (p.x, p.y) = (p.x - x0, p.y - y0);
+    }
+    if pl.iter().any(|p| p.x.max(p.y) >= max_coord as isize) {
+        return Err(GenerateError::CoordinatesTooSmall);
+    }
+    Ok(pl)
+}
+
+//Edge vector components of Valtr's construction. Sorted distinct values are split at random
+//between two chains running from the smallest to the largest, and the steps along one chain and
+//back along the other sum to zero
+fn valtr_steps<R: Rng + ?Sized>(n: usize, max_coord: usize, rng: &mut R) -> Vec<isize> {
+    let mut v: Vec<isize> = index::sample(rng, max_coord, n)
+        .into_iter()
+        .map(|x| x as isize)
+        .collect();
+    v.sort_unstable();
+    let (lo, hi) = (v[0], v[n - 1]);
+    let (mut up, mut down) = (lo, lo);
+    let mut r = Vec::with_capacity(n);
+    for &x in &v[1..n - 1] {
+        if rng.gen_bool(0.5) {
+            r.push(x - up);
+            up = x;
+        } else {
+            r.push(down - x);
+            down = x;
+        }
+    }
+    r.push(hi - up);
+    r.push(down - hi);
+    r
+}
+
+//Valtr's algorithm on the lattice. The x and y steps are paired at random and the vectors laid
+//end to end in angular order, which closes up into a convex polygon. Parallel vectors would
+//leave a straight angle, so one of them trades its y step with another vector, which keeps
+//both sums at zero
+pub fn convex<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<SimplePolygon, GenerateError> {
+    if n < 3 {
+        return Err(GenerateError::UnsupportedVertexCount(n));
+    }
+    if max_coord < n {
+        return Err(GenerateError::CoordinatesTooSmall);
+    }
+    let origin = Point::new(0, 0);
+    for _ in 0..RETRIES {
+        let xs = valtr_steps(n, max_coord, rng);
+        let mut ys = valtr_steps(n, max_coord, rng);
+        ys.shuffle(rng);
+        let mut v: Vec<Point> = xs
+            .into_iter()
+            .zip(ys)
+            .map(|(x, y)| Point::new(x, y))
+            .collect();
+        for _ in 0..RETRIES * n {
+            v.sort_by(|a, b| Point::angular_cmp(&origin, a, b));
+            let i = match (0..n).find(|&i| Point::cross(&origin, &v[i], &v[(i + 1) % n]) <= 0) {
+                Some(i) => i,
+                None => break,
+            };
+            let j = rng.gen_range(0..n);
+            let (a, b) = (v[i].y, v[j].y);
+            (v[i].y, v[j].y) = (b, a);
+        }
+        if (0..n).any(|i| Point::cross(&origin, &v[i], &v[(i + 1) % n]) <= 0) {
+            continue;
+        }
+        let mut pl = Vec::with_capacity(n);
+        let mut at = origin.clone();
+        for d in v {
+            pl.push(at.clone());
+            at = Point::new(at.x + d.x, at.y + d.y);
+        }
+        return Ok(SimplePolygon::from_point_list(fit(pl, max_coord)?));
+    }
+    Err(GenerateError::RetriesExhausted)
+}
+
+//Random points sorted by angle around the centre of the square, which then lies in the kernel.
+//No two points share a direction from the centre, and a draw is retried if some gap between
+//neighbours is not less than a half turn or three neighbours are in line
+pub fn star<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<SimplePolygon, GenerateError> {
+    if n < 3 {
+        return Err(GenerateError::UnsupportedVertexCount(n));
+    }
+    if max_coord < 3 || n >= max_coord * max_coord / 2 {
+        return Err(GenerateError::CoordinatesTooSmall);
+    }
+    let m = max_coord as isize;
+    let c = Point::new(m / 2, m / 2);
+    for _ in 0..RETRIES {
+        let mut directions = HashSet::new();
+        let mut pl = Vec::with_capacity(n);
+        let mut misses = 0;
+        while pl.len() < n && misses < RETRIES * n {
+            let p = Point::new(rng.gen_range(0..m), rng.gen_range(0..m));
+            let (dx, dy) = ((p.x - c.x) as i128, (p.y - c.y) as i128);
+            if (dx, dy) == (0, 0) || !directions.insert((dx / gcd(dx, dy), dy / gcd(dx, dy))) {
+                misses += 1;
+                continue;
+            }
+            pl.push(p);
+        }
+        if pl.len() < n {
+            return Err(GenerateError::RetriesExhausted);
+        }
+        pl.sort_by(|a, b| Point::angular_cmp(&c, a, b));
+        let ok = (0..n).all(|i| {
+            let (p, q, r) = (&pl[i], &pl[(i + 1) % n], &pl[(i + 2) % n]);
+            Point::cross(&c, p, q) > 0 && Point::cross(p, q, r) != 0
+        });
+        if ok {
+            return Ok(SimplePolygon::from_point_list(pl));
+        }
+    }
+    Err(GenerateError::RetriesExhausted)
+}
+
+//An x-monotone orthogonal polygon, cut into columns at random x positions. Between neighbouring
+//columns either the top or the bottom steps to a new height, keeping the two overlapping, and
+//each step adds two vertices. Half the time the result is turned on its side
+pub fn orthogonal<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<SimplePolygon, GenerateError> {
+    if n < 4 || !n.is_multiple_of(2) {
+        return Err(GenerateError::UnsupportedVertexCount(n));
+    }
+    let steps = (n - 4) / 2;
+    if max_coord < 3 || max_coord < steps + 2 {
+        return Err(GenerateError::CoordinatesTooSmall);
+    }
+    let m = max_coord as isize;
+    let mut xs: Vec<isize> = index::sample(rng, max_coord, steps + 2)
+        .into_iter()
+        .map(|x| x as isize)
+        .collect();
+    xs.sort_unstable();
+    let mut b = rng.gen_range(0..m - 1);
+    let mut t = rng.gen_range(b + 1..m);
+    let mut bottom = vec![Point::new(xs[0], b)];
+    let mut top = vec![Point::new(xs[0], t)];
+    for &x in &xs[1..=steps] {
+        //The top can move when there are two heights above the bottom to choose from, and the
+        //bottom when there are two below the top. With max_coord at least 3 one of them can
+        let (can_top, can_bottom) = (m - 1 - b >= 2, t >= 2);
+        if can_top && (!can_bottom || rng.gen_bool(0.5)) {
+            let u = loop {
+                let u = rng.gen_range(b + 1..m);
+                if u != t {
+                    break u;
+                }
+            };
+            top.extend([Point::new(x, t), Point::new(x, u)]);
+            t = u;
+        } else {
+            let u = loop {
+                let u = rng.gen_range(0..t);
+                if u != b {
+                    break u;
+                }
+            };
+            bottom.extend([Point::new(x, b), Point::new(x, u)]);
+            b = u;
+        }
+    }
+    let x = xs[steps + 1];
+    bottom.push(Point::new(x, b));
+    top.push(Point::new(x, t));
+    bottom.extend(top.into_iter().rev());
+    if rng.gen_bool(0.5) {
+        for p in &mut bottom {
+            (p.x, p.y) = (p.y, p.x);
+        }
+    }
+    Ok(SimplePolygon::from_point_list_ccw(bottom))
+}
+
+//A corridor of width 2 along a square spiral winding outwards. Every arm is longer than the
+//parallel one before it by at least 3, which keeps the turns 1 apart. One side of the corridor
+//is all convex corners and the other all reflex, n / 2 - 2 of them
+pub fn spiral<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<SimplePolygon, GenerateError> {
+    if n < 4 || !n.is_multiple_of(2) {
+        return Err(GenerateError::UnsupportedVertexCount(n));
+    }
+    let arms = n / 2 - 1;
+    let dirs = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+    let mut lengths: Vec<isize> = Vec::with_capacity(arms);
+    for j in 0..arms {
+        let step = rng.gen_range(3..=6);
+        lengths.push(if j < 2 { step } else { lengths[j - 2] + step });
+    }
+    //Corners of the centre line, then the corridor walls offset by the left normals
+    let mut centre = vec![Point::new(0, 0)];
+    for (j, &l) in lengths.iter().enumerate() {
+        let (dx, dy) = dirs[j % 4];
+        let p = centre.last().unwrap();
+        centre.push(Point::new(p.x + l * dx, p.y + l * dy));
+    }
+    let normal = |j: usize| {
+        let (dx, dy) = dirs[j % 4];
+        (-dy, dx)
+    };
+    let offset = |k: usize, side: isize| {
+        let (nx, ny) = if k == 0 {
+            normal(0)
+        } else if k == arms {
+            normal(arms - 1)
+        } else {
+            let (a, b) = (normal(k - 1), normal(k));
+            (a.0 + b.0, a.1 + b.1)
+        };
+        Point::new(centre[k].x + side * nx, centre[k].y + side * ny)
+    };
+    let mut pl: Vec<Point> = (0..=arms).map(|k| offset(k, -1)).collect();
+    pl.extend((0..=arms).rev().map(|k| offset(k, 1)));
+
+    //A random quarter turn, which keeps the orientation
+    for _ in 0..rng.gen_range(0..4) {
+        for p in &mut pl {
+            (p.x, p.y) = (-p.y, p.x);
+        }
+    }
+    Ok(SimplePolygon::from_point_list(fit(pl, max_coord)?))
+}
+
+//A base with n / 4 teeth standing on it, each gap between teeth cut down to its own depth. Each
+//gap has two reflex corners, the usual worst case for guarding
+pub fn comb<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<SimplePolygon, GenerateError> {
+    if n < 4 || !n.is_multiple_of(4) {
+        return Err(GenerateError::UnsupportedVertexCount(n));
+    }
+    let teeth = n / 4;
+    let h = max_coord as isize - 1;
+    if h < 3 {
+        return Err(GenerateError::CoordinatesTooSmall);
+    }
+    //Left edges of the teeth and gaps, from left to right, ending with the right end
+    let mut xs = vec![0];
+    for _ in 0..2 * teeth - 1 {
+        xs.push(xs.last().unwrap() + rng.gen_range(1..=3));
+    }
+    let width = *xs.last().unwrap();
+    if width > h {
+        return Err(GenerateError::CoordinatesTooSmall);
+    }
+    let mut pl = vec![Point::new(0, 0), Point::new(width, 0)];
+    //Walking back from the right along the top, tooth i spans xs[2i]..xs[2i + 1]
+    let right = |i: usize| if i + 1 == teeth { width } else { xs[2 * i + 1] };
+    for i in (0..teeth).rev() {
+        let top = rng.gen_range(h / 2 + 1..=h);
+        pl.push(Point::new(right(i), top));
+        pl.push(Point::new(xs[2 * i], top));
+        if i > 0 {
+            let depth = rng.gen_range(1..=h / 2);
+            pl.push(Point::new(xs[2 * i], depth));
+            pl.push(Point::new(xs[2 * i - 1], depth));
+        }
+    }
+    Ok(SimplePolygon::from_point_list(pl))
+}
+
+//n random points with no three in line, in O(n) time once the prime is found. They are distinct
+//points of a random conic modulo p, the largest prime up to max_coord: the parabola y = x^2 moved
+//by a random invertible affine map mod p. Three points in line over the integers are in line mod
+//p as well, and a line meets a conic mod p in at most two points. The affine map leaves no
+//preferred axis, so unlike the bare parabola the points have no column or mirror structure
+fn general_position<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<Vec<Point>, GenerateError> {
+    let p = (3..=max_coord).rev().find(|&p| is_prime(p));
+    let p = match p {
+        Some(p) if p >= n => p as u128,
+        _ => return Err(GenerateError::CoordinatesTooSmall),
+    };
+    let mut coef = || rng.gen_range(0..p);
+    let [a, b, c, d] = loop {
+        let m = [coef(), coef(), coef(), coef()];
+        if (m[0] * m[3] + p * p - m[1] * m[2]) % p != 0 {
+            break m;
+        }
+    };
+    let (e, f) = (coef(), coef());
+    Ok(index::sample(rng, p as usize, n)
+        .into_iter()
+        .map(|t| {
+            let (u, v) = (t as u128, t as u128 * t as u128 % p);
+            Point::new(
+                ((a * u + b * v + e) % p) as isize,
+                ((c * u + d * v + f) % p) as isize,
+            )
+        })
+        .collect())
+}
+
+fn is_prime(p: usize) -> bool {
+    p >= 2
+        && (2..)
+            .take_while(|d| d * d <= p)
+            .all(|d| !p.is_multiple_of(d))
+}
+
+//Auer and Held's space partitioning. Points are split by the line through two of them, and each
+//side is joined into a chain between the two. A chain from a to b through a set picks one point
+//s of it, cuts the set by a random line through s that crosses ab, and recurses on both halves,
+//so that every chain stays inside its own convex piece of the plane. Like quicksort this takes
+//O(n log n) expected time, so it is the family to use for 10^5 vertices and more, as long as
+//there is a prime of at least n up to max_coord
+pub fn space_partition<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<SimplePolygon, GenerateError> {
+    if n < 3 {
+        return Err(GenerateError::UnsupportedVertexCount(n));
+    }
+    for _ in 0..RETRIES {
+        let pts = general_position(n, max_coord, rng)?;
+        if let Some(order) = partition_order(&pts, rng) {
+            return Ok(SimplePolygon::from_point_list_ccw(
+                order.into_iter().map(|i| pts[i].clone()).collect(),
+            ));
+        }
+    }
+    Err(GenerateError::RetriesExhausted)
+}
+
+//Order in which space partitioning visits points in general position, or None if every cut
+//tried through some point ran into another
+fn partition_order<R: Rng + ?Sized>(pts: &[Point], rng: &mut R) -> Option<Vec<usize>> {
+    let n = pts.len();
+    let (a, b) = (0, 1);
+    let (right, left): (Vec<usize>, Vec<usize>) =
+        (2..n).partition(|&i| Point::cross(&pts[a], &pts[b], &pts[i]) < 0);
+
+    enum Task {
+        Chain(usize, usize, Vec<usize>),
+        Emit(usize),
+    }
+    //Tasks are popped from the end, so they are pushed in reverse
+    let mut stack = vec![
+        Task::Chain(b, a, left),
+        Task::Emit(b),
+        Task::Chain(a, b, right),
+        Task::Emit(a),
+    ];
+    let mut order = Vec::with_capacity(n);
+    const SCALE: i128 = 1 << 20;
+    while let Some(task) = stack.pop() {
+        let (a, b, set) = match task {
+            Task::Emit(p) => {
+                order.push(p);
+                continue;
+            }
+            Task::Chain(a, b, set) => (a, b, set),
+        };
+        if set.is_empty() {
+            continue;
+        }
+        let s = set[rng.gen_range(0..set.len())];
+        let (pa, pb, ps) = (&pts[a], &pts[b], &pts[s]);
+        //The line from s through q = a + (b - a) t / SCALE, everything scaled by SCALE
+        let mut halves = None;
+        for _ in 0..RETRIES {
+            let t = rng.gen_range(1..SCALE);
+            let qx = pa.x as i128 * SCALE + (pb.x - pa.x) as i128 * t;
+            let qy = pa.y as i128 * SCALE + (pb.y - pa.y) as i128 * t;
+            let side = |p: &Point| {
+                let (sx, sy) = (ps.x as i128 * SCALE, ps.y as i128 * SCALE);
+                ((qx - sx) * (p.y as i128 * SCALE - sy) - (qy - sy) * (p.x as i128 * SCALE - sx))
+                    .signum()
+            };
+            let a_side = side(pa);
+            let rest: Vec<(usize, i128)> = set
+                .iter()
+                .filter(|&&i| i != s)
+                .map(|&i| (i, side(&pts[i])))
+                .collect();
+            if rest.iter().all(|&(_, d)| d != 0) {
+                let (near, far): (Vec<_>, Vec<_>) =
+                    rest.into_iter().partition(|&(_, d)| d == a_side);
+                halves = Some((
+                    near.into_iter().map(|(i, _)| i).collect(),
+                    far.into_iter().map(|(i, _)| i).collect(),
+                ));
+                break;
+            }
+        }
+        let (near, far) = halves?;
+        stack.push(Task::Chain(s, b, far));
+        stack.push(Task::Emit(s));
+        stack.push(Task::Chain(a, s, near));
+    }
+    Some(order)
+}
+
+//Random points joined in random order, then untangled by 2-opt moves. Whenever two edges cross,
+//the path between them is reversed, which shortens the tour, so the process ends with a simple
+//polygon. Each pass over the edge pairs is O(n^2), which suits a few hundred vertices
+pub fn two_opt<R: Rng + ?Sized>(
+    n: usize,
+    max_coord: usize,
+    rng: &mut R,
+) -> Result<SimplePolygon, GenerateError> {
+    if n < 3 {
+        return Err(GenerateError::UnsupportedVertexCount(n));
+    }
+    let mut pl = general_position(n, max_coord, rng)?;
+    pl.shuffle(rng);
+    polygon::untangle(&mut pl);
+    debug_assert!((0..n).all(|i| {
+        Point::orientation(&pl[i], &pl[(i + 1) % n], &pl[(i + 2) % n])
+            != PointOrientation::Collinear
+    }));
+    Ok(SimplePolygon::from_point_list_ccw(pl))
+}
+
+#[cfg(test)]
+mod generate_tests {
+    use super::*;
+    use crate::primitives::Rational;
+
+    #[test]
+    fn test_families() {
+        let mut rng = StdRng::seed_from_u64(46);
+        let families = [
+            PolygonFamily::Convex,
+            PolygonFamily::Star,
+            PolygonFamily::Orthogonal,
+            PolygonFamily::Spiral,
+            PolygonFamily::Comb,
+            PolygonFamily::SpacePartition,
+            PolygonFamily::TwoOpt,
+        ];
+        for family in families {
+            let (p, stats) = generate(family, 12, 100, &mut rng).unwrap();
+            assert_eq!(stats.vertex_count, 12);
+            assert!(p
+                .get_point_list()
+                .iter()
+                .all(|q| q.x.min(q.y) >= 0 && q.x.max(q.y) < 100));
+            //A simple counterclockwise polygon triangulates into n - 2 triangles
+            let dcel = p.triangulate();
+            assert!(dcel.validate().is_empty(), "{:?}", family);
+            assert_eq!(dcel.get_internal_faces().len(), 10, "{:?}", family);
+            assert_eq!(
+                stats.split_count + stats.start_count,
+                stats.merge_count + stats.end_count
+            );
+            match family {
+                PolygonFamily::Convex => assert_eq!(stats.reflex_count, 0),
+                PolygonFamily::Orthogonal => assert_eq!(stats.reflex_count, 4),
+                PolygonFamily::Spiral | PolygonFamily::Comb => assert_eq!(stats.reflex_count, 4),
+                _ => {}
+            }
+        }
+        let a = generate_seeded(PolygonFamily::TwoOpt, 30, 100, 47).unwrap();
+        let b = generate_seeded(PolygonFamily::TwoOpt, 30, 100, 47).unwrap();
+        assert_eq!(a.0.get_point_list(), b.0.get_point_list());
+        assert_eq!(
+            comb(10, 100, &mut rng).err(),
+            Some(GenerateError::UnsupportedVertexCount(10))
+        );
+    }
+    #[test]
+    fn test_large_space_partition() {
+        let mut rng = StdRng::seed_from_u64(48);
+        let n = 100_000;
+        let p = space_partition(n, 1_000_000, &mut rng).unwrap();
+        let pl = p.get_point_list();
+        assert_eq!(pl.len(), n);
+        assert!(pl
+            .iter()
+            .all(|q| q.x.min(q.y) >= 0 && q.x.max(q.y) < 1_000_000));
+        assert!(p.signed_area() > Rational::from_int(0));
+        assert!(polygon::is_simple(pl));
+        //No prime up to 100 reaches 5000, and 97 is the largest one
+        assert_eq!(
+            space_partition(5000, 100, &mut rng).err(),
+            Some(GenerateError::CoordinatesTooSmall)
+        );
+        assert!(two_opt(97, 100, &mut rng).is_ok());
+        assert_eq!(
+            two_opt(98, 100, &mut rng).err(),
+            Some(GenerateError::CoordinatesTooSmall)
+        );
+    }
+}
@@ -0,0 +1,429 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// guard.rs - Guarding a polygon from its vertices (art gallery problem)
+use crate::dcel::Colour;
+use crate::homogeneous::{Line, Overflow, Vertex};
+use crate::polygon::SimplePolygon;
+use crate::primitives::Point;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct GuardSet {
+    //Indices into the polygon's point list
+    pub guards: Vec<usize>,
+    //Triangles of the triangulation given to each guard, as point list indices. A triangle that
+    //no single guard sees completely is given to each of the guards that see it between them
+    pub coverage: HashMap<usize, Vec<[usize; 3]>>,
+}
+
+//Convex part of a triangle, as corners each with the line to the next corner
+type Cell = Vec<(Vertex, Line)>;
+//For every triangle, the guards that see each of its cells
+type SeenBy = Vec<Vec<Vec<usize>>>;
+
+#[derive(Debug, PartialEq)]
+pub enum GuardError {
+    //Triangles of the triangulation with a part that no guard sees
+    Uncovered(Vec<[usize; 3]>),
+    //Cutting the triangles along the sight lines needs more than i128
+    CoordinatesTooLarge,
+}
+
+impl From<Overflow> for GuardError {
+    fn from(_: Overflow) -> Self {
+        GuardError::CoordinatesTooLarge
+    }
+}
+
+impl SimplePolygon {
+    //Fisk's argument: every triangle of a three-coloured triangulation has one corner of each
+    //colour, so the smallest colour class sees the whole polygon and has at most n/3 vertices
+    pub fn guard_set(&self) -> GuardSet {
+        let dcel = self.triangulate();
+        let colouring = dcel.three_color();
+        let smallest = Colour::ALL
+            .into_iter()
+            .min_by_key(|c| colouring.values().filter(|x| *x == c).count())
+            .unwrap();
+
+        let mut guards: Vec<usize> = colouring
+            .iter()
+            .filter(|(_, c)| **c == smallest)
+            .map(|(&i, _)| i)
+            .collect();
+        guards.sort_unstable();
+
+        let mut coverage: HashMap<usize, Vec<[usize; 3]>> = HashMap::new();
+        for face in dcel.get_internal_faces() {
+            let t = dcel.get_index_list(face);
+            let g = *t.iter().find(|i| colouring[i] == smallest).unwrap();
+            coverage.entry(g).or_default().push([t[0], t[1], t[2]]);
+        }
+        GuardSet { guards, coverage }
+    }
+
+    //Certifies that the guards see the whole polygon. Each triangle of the triangulation is cut
+    //along the guards' sight lines into cells that every guard sees either completely or not at
+    //all, so the triangle is covered exactly when every cell is seen by some guard. Triangles
+    //with a cell no guard sees are returned as GuardError::Uncovered. Cell corners meet two lines
+    //through input points, so this is exact for the same coordinates as the kernel, and gives
+    //GuardError::CoordinatesTooLarge where the arithmetic would leave i128
+    pub fn verify_guards(&self, guards: &[usize]) -> Result<GuardSet, GuardError> {
+        let (triangles, seen_by) = self.guard_visibility(guards)?;
+        let uncovered: Vec<[usize; 3]> = triangles
+            .iter()
+            .zip(&seen_by)
+            .filter(|(_, cells)| cells.iter().any(|c| c.is_empty()))
+            .map(|(&t, _)| t)
+            .collect();
+        if !uncovered.is_empty() {
+            return Err(GuardError::Uncovered(uncovered));
+        }
+        Ok(GuardSet {
+            guards: guards.to_vec(),
+            coverage: assign_triangles(triangles, &seen_by, guards),
+        })
+    }
+
+    //Greedily drops guards all of whose cells are seen by other remaining guards, trying the
+    //guards that see the least first
+    pub fn prune_guards(&self, guards: &[usize]) -> Result<GuardSet, GuardError> {
+        self.verify_guards(guards)?;
+        let (triangles, seen_by) = self.guard_visibility(guards)?;
+        let mut sees: HashMap<usize, Vec<(usize, usize)>> =
+            guards.iter().map(|&g| (g, vec![])).collect();
+        let mut count: Vec<Vec<usize>> = Vec::new();
+        for (t, cells) in seen_by.iter().enumerate() {
+            for (c, s) in cells.iter().enumerate() {
+                for g in s {
+                    sees.get_mut(g).unwrap().push((t, c));
+                }
+            }
+            count.push(cells.iter().map(|s| s.len()).collect());
+        }
+        let mut order = guards.to_vec();
+        order.sort_by_key(|g| (sees[g].len(), *g));
+
+        let mut kept = Vec::new();
+        for g in order {
+            if sees[&g].iter().all(|&(t, c)| count[t][c] > 1) {
+                for &(t, c) in &sees[&g] {
+                    count[t][c] -= 1;
+                }
+            } else {
+                kept.push(g);
+            }
+        }
+        kept.sort_unstable();
+        Ok(GuardSet {
+            coverage: assign_triangles(triangles, &seen_by, &kept),
+            guards: kept,
+        })
+    }
+
+    //Triangles of the triangulation, each cut into cells listed with the guards that see them
+    fn guard_visibility(&self, guards: &[usize]) -> Result<(Vec<[usize; 3]>, SeenBy), Overflow> {
+        let pl = self.get_point_list();
+        let dcel = self.triangulate();
+        let triangles: Vec<[usize; 3]> = dcel
+            .get_internal_faces()
+            .into_iter()
+            .map(|face| {
+                let t = dcel.get_index_list(face);
+                [t[0], t[1], t[2]]
+            })
+            .collect();
+        let mut across: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (k, t) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                across
+                    .entry(side_key(t[i], t[(i + 1) % 3]))
+                    .or_default()
+                    .push(k);
+            }
+        }
+        let sights: Vec<Vec<Option<Sight>>> = guards
+            .iter()
+            .map(|&g| sights_from(pl, &triangles, &across, g))
+            .collect();
+
+        let mut seen_by = Vec::new();
+        for (k, t) in triangles.iter().enumerate() {
+            let mut cell = Vec::new();
+            for i in 0..3 {
+                let line = Line::through_points(&pl[t[i]], &pl[t[(i + 1) % 3]])?;
+                cell.push((Vertex::from_point(&pl[t[i]]), line));
+            }
+            //The guards that see some of the triangle, with the sight lines that bound what
+            //they see, or None when they see all of it
+            let mut bounds = Vec::new();
+            for (&g, s) in guards.iter().zip(&sights) {
+                match &s[k] {
+                    Some(Sight::All) => bounds.push((g, None)),
+                    Some(Sight::Between(right, left)) => bounds.push((
+                        g,
+                        Some((
+                            Line::through_points(&pl[g], right)?,
+                            Line::through_points(&pl[g], left)?,
+                        )),
+                    )),
+                    None => {}
+                }
+            }
+            let mut cells = vec![cell];
+            for (right, left) in bounds.iter().filter_map(|(_, b)| b.as_ref()) {
+                for h in [right, left] {
+                    let mut parts = Vec::new();
+                    for c in cells {
+                        match split(&c, h)? {
+                            Some(halves) => parts.extend(halves),
+                            None => parts.push(c),
+                        }
+                    }
+                    cells = parts;
+                }
+            }
+            let mut seen = Vec::new();
+            for c in &cells {
+                let mut s = Vec::new();
+                for (g, b) in &bounds {
+                    let sees = match b {
+                        None => true,
+                        Some((right, left)) => {
+                            let mut inside = true;
+                            for (v, _) in c {
+                                inside &= right.side(v)? >= 0 && left.side(v)? <= 0;
+                            }
+                            inside
+                        }
+                    };
+                    if sees {
+                        s.push(*g);
+                    }
+                }
+                seen.push(s);
+            }
+            seen_by.push(seen);
+        }
+        Ok((triangles, seen_by))
+    }
+}
+
+//Part of a triangle a guard sees. A guard sees all of the triangles it is a corner of, and
+//beyond those, sight lines have to pass through every diagonal on the way, so the part seen is
+//the triangle cut down to the rays from the guard through right and left, counterclockwise
+#[derive(Debug, Clone)]
+enum Sight {
+    All,
+    Between(Point, Point),
+}
+
+fn side_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+//What guard g sees of every triangle, found by walking the dual tree outwards from the
+//triangles around g and narrowing the sight lines at each diagonal crossed
+fn sights_from(
+    pl: &[Point],
+    triangles: &[[usize; 3]],
+    across: &HashMap<(usize, usize), Vec<usize>>,
+    g: usize,
+) -> Vec<Option<Sight>> {
+    let q = &pl[g];
+    //Sight lines through the segment from a to b, narrowed to those already between right and
+    //left. Both lie beyond the diagonal last crossed, so all directions compared are within a
+    //half turn of each other
+    let narrow = |bound: Option<(&Point, &Point)>, a: &Point, b: &Point| {
+        let (mut right, mut left) = match Point::cross(q, a, b).cmp(&0) {
+            Ordering::Greater => (a, b),
+            Ordering::Less => (b, a),
+            Ordering::Equal => return None,
+        };
+        if let Some((r, l)) = bound {
+            if Point::cross(q, right, r) > 0 {
+                right = r;
+            }
+            if Point::cross(q, left, l) < 0 {
+                left = l;
+            }
+        }
+        (Point::cross(q, right, left) > 0).then(|| Sight::Between(right.clone(), left.clone()))
+    };
+    let neighbour =
+        |k: usize, a: usize, b: usize| across[&side_key(a, b)].iter().copied().find(|&j| j != k);
+
+    let mut sights = vec![None; triangles.len()];
+    let mut stack = Vec::new();
+    for (k, t) in triangles.iter().enumerate() {
+        if let Some(i) = t.iter().position(|&v| v == g) {
+            sights[k] = Some(Sight::All);
+            let (a, b) = (t[(i + 1) % 3], t[(i + 2) % 3]);
+            if let (Some(j), Some(s)) = (neighbour(k, a, b), narrow(None, &pl[a], &pl[b])) {
+                stack.push((j, side_key(a, b), s));
+            }
+        }
+    }
+    while let Some((k, from, s)) = stack.pop() {
+        let t = triangles[k];
+        if let Sight::Between(r, l) = &s {
+            for i in 0..3 {
+                let (a, b) = (t[i], t[(i + 1) % 3]);
+                if side_key(a, b) == from {
+                    continue;
+                }
+                if let (Some(j), Some(s)) =
+                    (neighbour(k, a, b), narrow(Some((r, l)), &pl[a], &pl[b]))
+                {
+                    stack.push((j, side_key(a, b), s));
+                }
+            }
+        }
+        sights[k] = Some(s);
+    }
+    sights
+}
+
+//Cuts a convex cell, given as corners each with the line to the next corner, in two along h.
+//None when h does not pass through the inside of the cell
+fn split(cell: &[(Vertex, Line)], h: &Line) -> Result<Option<[Cell; 2]>, Overflow> {
+    let n = cell.len();
+    let mut s = Vec::new();
+    for (v, _) in cell {
+        s.push(h.side(v)?);
+    }
+    if !s.contains(&1) || !s.contains(&-1) {
+        return Ok(None);
+    }
+    //Corners of each side, marking those on h
+    let mut parts = [Vec::new(), Vec::new()];
+    for i in 0..n {
+        let (v, line) = cell[i];
+        if s[i] >= 0 {
+            parts[0].push((v, line, s[i] == 0));
+        }
+        if s[i] <= 0 {
+            parts[1].push((v, line, s[i] == 0));
+        }
+        if s[i] * s[(i + 1) % n] < 0 {
+            //The side runs from one side of h to the other, so it is not parallel to h
+            let x = h.meet(&line)?.unwrap();
+            parts[0].push((x, line, true));
+            parts[1].push((x, line, true));
+        }
+    }
+    //Each side has exactly two corners on h, next to each other, joined along h
+    Ok(Some(parts.map(|p| {
+        (0..p.len())
+            .map(|k| {
+                let (v, line, on) = p[k];
+                let along = on && p[(k + 1) % p.len()].2;
+                (v, if along { *h } else { line })
+            })
+            .collect()
+    })))
+}
+
+//Gives every triangle to guards that together see all of its cells, greedily taking the guard
+//that sees the most cells still unseen
+fn assign_triangles(
+    triangles: Vec<[usize; 3]>,
+    seen_by: &[Vec<Vec<usize>>],
+    guards: &[usize],
+) -> HashMap<usize, Vec<[usize; 3]>> {
+    let mut coverage: HashMap<usize, Vec<[usize; 3]>> = HashMap::new();
+    for (t, cells) in triangles.into_iter().zip(seen_by) {
+        let mut unseen: Vec<&Vec<usize>> = cells.iter().collect();
+        while !unseen.is_empty() {
+            let g = *guards
+                .iter()
+                .max_by_key(|g| {
+                    let seen = unseen.iter().filter(|s| s.contains(g)).count();
+                    (seen, std::cmp::Reverse(**g))
+                })
+                .unwrap();
+            unseen.retain(|s| !s.contains(&g));
+            coverage.entry(g).or_default().push(t);
+        }
+    }
+    coverage
+}
+
+#[cfg(test)]
+mod guard_tests {
+    use super::*;
+    use crate::fixtures::{comb, points};
+    use crate::primitives::Point;
+    #[test]
+    fn test_guard_set() {
+        //A comb, whose teeth cannot all be seen from one vertex
+        let p = comb();
+        let n = p.get_point_list().len();
+        let g = p.guard_set();
+        assert!(!g.guards.is_empty() && g.guards.len() <= n / 3);
+        assert_eq!(g.coverage.len(), g.guards.len());
+
+        let triangles: Vec<&[usize; 3]> = g.coverage.values().flatten().collect();
+        assert_eq!(triangles.len(), n - 2);
+        for (guard, ts) in &g.coverage {
+            assert!(ts.iter().all(|t| t.contains(guard)));
+        }
+    }
+    #[test]
+    fn test_guard_set_clockwise() {
+        let pl: Vec<Point> = comb().get_point_list().iter().rev().cloned().collect();
+        let n = pl.len();
+        let p = SimplePolygon::from_point_list(pl);
+        let g = p.guard_set();
+        assert!(!g.guards.is_empty() && g.guards.len() <= n / 3);
+        let triangles: Vec<&[usize; 3]> = g.coverage.values().flatten().collect();
+        assert_eq!(triangles.len(), n - 2);
+        assert!(p.verify_guards(&g.guards).is_ok());
+    }
+    #[test]
+    fn test_prune_guards() {
+        //Convex, so any one vertex is enough
+        let pl: Vec<Point> = [(0, 0), (4, 0), (6, 2), (6, 5), (3, 7), (0, 4)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        let p = SimplePolygon::from_point_list(pl);
+        let g = p.prune_guards(&[1, 3, 5]).unwrap();
+        assert_eq!(g.guards.len(), 1);
+        assert_eq!(g.coverage[&g.guards[0]].len(), 4);
+
+        //The tooth tip (4, 6) cannot see into the notch between (4, 1) and (8, 1)
+        let pl: Vec<Point> = [(0, 0), (12, 0), (12, 6), (8, 1), (4, 6), (0, 1)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        let p = SimplePolygon::from_point_list(pl);
+        assert!(p.verify_guards(&[4]).is_err());
+        let g = p.prune_guards(&[0, 1, 3]).unwrap();
+        assert_eq!(g.guards, vec![3]);
+    }
+    #[test]
+    fn test_guards_sharing_a_triangle() {
+        //Below the notch, (12, 6) sees the part right of the line to (8, 1) and (4, 6) the part
+        //left of it, so the bottom triangles are only covered by the two together
+        let p = SimplePolygon::from_point_list(points(&[
+            (0, 0),
+            (12, 0),
+            (12, 6),
+            (8, 1),
+            (4, 6),
+            (0, 1),
+        ]));
+        assert!(p.verify_guards(&[2]).is_err());
+        assert!(p.verify_guards(&[4]).is_err());
+        let g = p.verify_guards(&[2, 4]).unwrap();
+        let shared: Vec<&[usize; 3]> = g.coverage[&2]
+            .iter()
+            .filter(|t| g.coverage[&4].contains(t))
+            .collect();
+        assert!(!shared.is_empty());
+        assert!(shared.iter().all(|t| !t.contains(&2) && !t.contains(&4)));
+        assert_eq!(p.prune_guards(&[2, 4]).unwrap().guards, vec![2, 4]);
+    }
+}
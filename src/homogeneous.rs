@@ -0,0 +1,149 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// homogeneous.rs - Exact points and lines in homogeneous coordinates, with checked arithmetic
+use crate::primitives::{gcd, Point, Rational, RationalPoint};
+use std::cmp::Ordering;
+
+//Some product or sum left i128. Each caller turns this into its own error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Overflow;
+
+pub(crate) fn mul(a: i128, b: i128) -> Result<i128, Overflow> {
+    a.checked_mul(b).ok_or(Overflow)
+}
+
+//a d - b c
+pub(crate) fn det(a: i128, b: i128, c: i128, d: i128) -> Result<i128, Overflow> {
+    mul(a, d)?.checked_sub(mul(b, c)?).ok_or(Overflow)
+}
+
+//The point (x / w, y / w), in lowest terms with w positive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Vertex {
+    pub(crate) x: i128,
+    pub(crate) y: i128,
+    pub(crate) w: i128,
+}
+
+impl Vertex {
+    pub(crate) fn new(x: i128, y: i128, w: i128) -> Self {
+        let g = gcd(gcd(x, y), w) * w.signum();
+        Self {
+            x: x / g,
+            y: y / g,
+            w: w / g,
+        }
+    }
+
+    pub(crate) fn from_point(p: &Point) -> Self {
+        Self::new(p.x as i128, p.y as i128, 1)
+    }
+
+    pub(crate) fn to_rational(self) -> RationalPoint {
+        RationalPoint::new(Rational::new(self.x, self.w), Rational::new(self.y, self.w))
+    }
+
+    //Offset from p, scaled by w
+    pub(crate) fn from(&self, p: &Point) -> Result<(i128, i128), Overflow> {
+        let dx = self.x.checked_sub(mul(p.x as i128, self.w)?);
+        let dy = self.y.checked_sub(mul(p.y as i128, self.w)?);
+        Ok((dx.ok_or(Overflow)?, dy.ok_or(Overflow)?))
+    }
+
+    //Order by x and then by y
+    pub(crate) fn cmp_xy(&self, other: &Self) -> Result<Ordering, Overflow> {
+        let (x, y) = (mul(self.x, other.w)?, mul(self.y, other.w)?);
+        let (ox, oy) = (mul(other.x, self.w)?, mul(other.y, self.w)?);
+        Ok(x.cmp(&ox).then(y.cmp(&oy)))
+    }
+}
+
+//The line a x + b y + c = 0 in lowest terms, with its left side positive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Line {
+    a: i128,
+    b: i128,
+    c: i128,
+}
+
+impl Line {
+    pub(crate) fn through(p: &Vertex, q: &Vertex) -> Result<Self, Overflow> {
+        let (a, b, c) = (
+            det(p.y, p.w, q.y, q.w)?,
+            det(p.w, p.x, q.w, q.x)?,
+            det(p.x, p.y, q.x, q.y)?,
+        );
+        let g = gcd(gcd(a, b), c).max(1);
+        Ok(Self {
+            a: a / g,
+            b: b / g,
+            c: c / g,
+        })
+    }
+
+    pub(crate) fn through_points(p: &Point, q: &Point) -> Result<Self, Overflow> {
+        Self::through(&Vertex::from_point(p), &Vertex::from_point(q))
+    }
+
+    pub(crate) fn reversed(self) -> Self {
+        Self {
+            a: -self.a,
+            b: -self.b,
+            c: -self.c,
+        }
+    }
+
+    //Sign of how far left of the line v is
+    pub(crate) fn side(&self, v: &Vertex) -> Result<i128, Overflow> {
+        let s = [mul(self.a, v.x)?, mul(self.b, v.y)?, mul(self.c, v.w)?]
+            .into_iter()
+            .try_fold(0i128, i128::checked_add)
+            .ok_or(Overflow)?;
+        Ok(s.signum())
+    }
+
+    //None for parallel lines
+    pub(crate) fn meet(&self, other: &Line) -> Result<Option<Vertex>, Overflow> {
+        let w = det(self.a, self.b, other.a, other.b)?;
+        if w == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Vertex::new(
+            det(self.b, self.c, other.b, other.c)?,
+            det(self.c, self.a, other.c, other.a)?,
+            w,
+        )))
+    }
+}
+
+//Convex hull, counterclockwise and without collinear corners
+pub(crate) fn hull(mut points: Vec<Vertex>) -> Result<Vec<Vertex>, Overflow> {
+    let mut overflow = false;
+    points.sort_by(|p, q| {
+        p.cmp_xy(q).unwrap_or_else(|_| {
+            overflow = true;
+            Ordering::Equal
+        })
+    });
+    if overflow {
+        return Err(Overflow);
+    }
+    points.dedup();
+    if points.len() < 3 {
+        return Ok(points);
+    }
+    let mut h: Vec<Vertex> = Vec::new();
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let base = h.len();
+        for p in pass {
+            while h.len() >= base + 2
+                && Line::through(&h[h.len() - 2], &h[h.len() - 1])?.side(&p)? <= 0
+            {
+                h.pop();
+            }
+            h.push(p);
+        }
+        h.pop();
+    }
+    Ok(h)
+}
@@ -0,0 +1,199 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// hull.rs - Convex hull of a polygon and the pockets between the hull and the polygon
+use crate::dcel::DCEL;
+use crate::polygon::{is_clockwise, SimplePolygon};
+use crate::primitives::{DirEdge, Point};
+use std::collections::VecDeque;
+
+//Melkman's algorithm. The deque holds the hull of the chain read so far, with the newest vertex
+//at both ends, and a vertex that falls inside the wedge at that vertex can be skipped. Only a
+//simple chain guarantees the skipped vertices stay inside, which is what makes this O(n).
+//Returns the hull as polygon indices in counterclockwise order, without collinear vertices
+fn melkman(pl: &[Point]) -> Vec<usize> {
+    let n = pl.len();
+    //Start where three consecutive vertices make a proper turn
+    let start =
+        match (0..n).find(|&i| Point::cross(&pl[i], &pl[(i + 1) % n], &pl[(i + 2) % n]) != 0) {
+            Some(i) => i,
+            None => return Vec::new(),
+        };
+    let at = |k: usize| (start + k) % n;
+    let left = |a: usize, b: usize, c: usize| Point::cross(&pl[a], &pl[b], &pl[c]) > 0;
+
+    let (v0, v1, v2) = (at(0), at(1), at(2));
+    let mut d: VecDeque<usize> = if left(v0, v1, v2) {
+        VecDeque::from([v2, v0, v1, v2])
+    } else {
+        VecDeque::from([v2, v1, v0, v2])
+    };
+    for k in 3..n {
+        let v = at(k);
+        //The front of the deque is its bottom and the back its top
+        let t = d.len() - 1;
+        if left(d[t - 1], d[t], v) && left(d[0], d[1], v) {
+            continue;
+        }
+        while !left(d[d.len() - 2], d[d.len() - 1], v) {
+            d.pop_back();
+        }
+        d.push_back(v);
+        while !left(v, d[0], d[1]) {
+            d.pop_front();
+        }
+        d.push_front(v);
+    }
+    //Bottom to top the deque runs counterclockwise, repeating its first vertex at the end
+    //A vertex met in line with the hull edge at either end can still sit there, so drop those
+    let d: Vec<usize> = d.into_iter().skip(1).collect();
+    let m = d.len();
+    let mut hull: Vec<usize> = (0..m)
+        .filter(|&i| left(d[(i + m - 1) % m], d[i], d[(i + 1) % m]))
+        .map(|i| d[i])
+        .collect();
+    let lowest = (0..hull.len()).min_by_key(|&i| hull[i]).unwrap();
+    hull.rotate_left(lowest);
+    hull
+}
+
+//What to add to an index, modulo n, to step counterclockwise round the polygon
+fn ccw_offset(pl: &[Point]) -> usize {
+    if is_clockwise(pl) {
+        pl.len() - 1
+    } else {
+        1
+    }
+}
+
+//Polygon indices on the boundary of the hull in counterclockwise order, counting those that lie
+//along a hull edge
+pub(crate) fn hull_boundary(pl: &[Point]) -> Vec<usize> {
+    let n = pl.len();
+    let hull = melkman(pl);
+    let d = ccw_offset(pl);
+    let mut r = Vec::new();
+    for (k, &a) in hull.iter().enumerate() {
+        let b = hull[(k + 1) % hull.len()];
+        r.push(a);
+        let mut i = (a + d) % n;
+        while i != b {
+            if Point::cross(&pl[a], &pl[b], &pl[i]) == 0 {
+                r.push(i);
+            }
+            i = (i + d) % n;
+        }
+    }
+    r
+}
+
+impl SimplePolygon {
+    //Smallest convex polygon holding this one, in O(n) by Melkman's algorithm. Its vertices
+    //are vertices of the polygon, counterclockwise and without collinear ones
+    pub fn convex_hull(&self) -> SimplePolygon {
+        let pl = self.get_point_list();
+        SimplePolygon::from_point_list(melkman(pl).iter().map(|&i| pl[i].clone()).collect())
+    }
+
+    //Lids of the pockets, as pairs of polygon indices (a, b) where the boundary from a to b
+    //bends inwards away from the hull. Polygon vertices lying on a hull edge split it, so that
+    //every pocket touches its lid only at the ends
+    pub fn pocket_lids(&self) -> Vec<(usize, usize)> {
+        let pl = self.get_point_list();
+        let (n, d) = (pl.len(), ccw_offset(pl));
+        let boundary = hull_boundary(pl);
+        let m = boundary.len();
+        (0..m)
+            .map(|k| (boundary[k], boundary[(k + 1) % m]))
+            .filter(|&(a, b)| (a + d) % n != b)
+            .collect()
+    }
+
+    //Regions between the hull and the polygon, each as a counterclockwise polygon that starts
+    //with its lid
+    pub fn pockets(&self) -> Vec<SimplePolygon> {
+        let pl = self.get_point_list();
+        let n = pl.len();
+        //Back along the polygon, from b to a
+        let d = n - ccw_offset(pl);
+        self.pocket_lids()
+            .into_iter()
+            .map(|(a, b)| {
+                let mut r = vec![pl[a].clone()];
+                let mut i = b;
+                while i != a {
+                    r.push(pl[i].clone());
+                    i = (i + d) % n;
+                }
+                SimplePolygon::from_point_list(r)
+            })
+            .collect()
+    }
+}
+
+impl DCEL {
+    //Subdivision of the polygon with its pocket lids drawn in, so that the region outside the
+    //polygon is split into one face per pocket and the unbounded face outside the hull. Vertex
+    //indices follow the polygon
+    pub fn from_polygon_with_pockets(p: &SimplePolygon) -> Self {
+        let pl = p.get_point_list();
+        let mut segments: Vec<DirEdge> = (0..pl.len())
+            .map(|i| DirEdge::from_points(&pl[i], &pl[(i + 1) % pl.len()]))
+            .collect();
+        for (a, b) in p.pocket_lids() {
+            segments.push(DirEdge::from_points(&pl[a], &pl[b]));
+        }
+        DCEL::from_planar_graph(&segments, &[]).expect("Pocket lids cross the polygon")
+    }
+}
+
+#[cfg(test)]
+mod hull_tests {
+    use super::*;
+    use crate::fixtures::{notched_square, points};
+    #[test]
+    fn test_convex_hull() {
+        let p = notched_square();
+        assert_eq!(
+            p.convex_hull().get_point_list(),
+            &points(&[(0, 0), (10, 0), (10, 10), (0, 10)])
+        );
+        assert_eq!(p.pocket_lids(), vec![(3, 6)]);
+        let pockets = p.pockets();
+        assert_eq!(pockets.len(), 1);
+        assert_eq!(
+            pockets[0].get_point_list(),
+            &points(&[(6, 10), (4, 10), (4, 4), (6, 4)])
+        );
+
+        let dcel = DCEL::from_polygon_with_pockets(&p);
+        assert!(dcel.validate().is_empty());
+        assert_eq!(dcel.get_internal_faces().len(), 2);
+
+        //A zigzag whose dents are all pockets, and a point on the hull that is dropped
+        let z = SimplePolygon::from_point_list(points(&[
+            (0, 0),
+            (4, 0),
+            (8, 0),
+            (8, 6),
+            (6, 2),
+            (4, 6),
+            (2, 2),
+            (0, 6),
+        ]));
+        assert_eq!(
+            z.convex_hull().get_point_list(),
+            &points(&[(0, 0), (8, 0), (8, 6), (0, 6)])
+        );
+        assert_eq!(z.pocket_lids(), vec![(3, 5), (5, 7)]);
+        assert_eq!(z.pockets().len(), 2);
+
+        //The same pockets come out when the boundary runs clockwise
+        let pl: Vec<Point> = p.get_point_list().iter().rev().cloned().collect();
+        let cw = SimplePolygon::from_point_list(pl);
+        assert_eq!(cw.pocket_lids(), vec![(4, 1)]);
+        assert_eq!(
+            cw.pockets()[0].get_point_list(),
+            pockets[0].get_point_list()
+        );
+    }
+}
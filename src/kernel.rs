@@ -0,0 +1,395 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// kernel.rs - Kernel of a polygon, the region from which all of it is visible
+use crate::homogeneous::{det, mul, Line, Overflow, Vertex};
+use crate::polygon::{is_clockwise, RationalPolygon, SimplePolygon};
+use crate::primitives::Point;
+use std::cmp::Ordering;
+
+//Every vertex of the kernel is the meeting of two input lines, so its coordinates stay small
+//however many cuts are made. While no input coordinate is beyond this in absolute value, x and y
+//stay below 2^62 and w below 2^41, which keeps every product taken below exact in i128
+const MAX_COORD: i128 = 1 << 19;
+
+#[derive(Debug, PartialEq)]
+pub enum KernelError {
+    CoordinatesTooLarge,
+}
+
+impl From<Overflow> for KernelError {
+    fn from(_: Overflow) -> Self {
+        KernelError::CoordinatesTooLarge
+    }
+}
+
+//Signs of the cross and dot products of a - v and b - v
+fn turn(v: &Point, a: &Vertex, b: &Vertex) -> Result<(i128, i128), Overflow> {
+    let ((ax, ay), (bx, by)) = (a.from(v)?, b.from(v)?);
+    let dot = mul(ax, bx)?.checked_add(mul(ay, by)?).ok_or(Overflow)?;
+    Ok((det(ax, ay, bx, by)?.signum(), dot.signum()))
+}
+
+//Whether b is nearer to v than a, for a and b on the same ray from v
+fn is_nearer(v: &Point, a: &Vertex, b: &Vertex) -> Result<bool, Overflow> {
+    let ((ax, ay), (bx, by)) = (a.from(v)?, b.from(v)?);
+    Ok(if ax != 0 {
+        mul(bx.abs(), a.w)? < mul(ax.abs(), b.w)?
+    } else {
+        mul(by.abs(), a.w)? < mul(ay.abs(), b.w)?
+    })
+}
+
+//Corner of the region cut so far, with the line its counterclockwise side lies on
+#[derive(Debug, Clone)]
+struct Node {
+    v: Vertex,
+    line: Line,
+    prev: usize,
+    next: usize,
+}
+
+//The region cut so far, a convex polygon of linked corners
+struct Region {
+    nodes: Vec<Node>,
+}
+
+impl Region {
+    fn next(&self, k: usize) -> usize {
+        self.nodes[k].next
+    }
+
+    fn prev(&self, k: usize) -> usize {
+        self.nodes[k].prev
+    }
+
+    //Removes the run of corners strictly right of the cut around t, and joins the ends along
+    //the cut. Returns the corners that start and end the new side, or None if nothing is left
+    fn cut(&mut self, cut: &Line, t: usize) -> Result<Option<(usize, usize)>, Overflow> {
+        let side = |r: &Self, k: usize| cut.side(&r.nodes[k].v);
+        let (mut first, mut last) = (t, t);
+        while side(self, self.next(last))? < 0 {
+            last = self.next(last);
+            if last == t {
+                return Ok(None);
+            }
+        }
+        while side(self, self.prev(first))? < 0 {
+            first = self.prev(first);
+        }
+        let (before, after) = (self.prev(first), self.next(last));
+        //The corners either side of the run are not right of the cut and the run is, so the
+        //sides leading out of it cross the cut
+        let enter = if side(self, before)? == 0 {
+            self.nodes[before].line = *cut;
+            before
+        } else {
+            self.nodes.push(Node {
+                v: cut.meet(&self.nodes[before].line)?.unwrap(),
+                line: *cut,
+                prev: before,
+                next: 0,
+            });
+            self.nodes[before].next = self.nodes.len() - 1;
+            self.nodes.len() - 1
+        };
+        let leave = if side(self, after)? == 0 {
+            after
+        } else {
+            self.nodes.push(Node {
+                v: cut.meet(&self.nodes[last].line)?.unwrap(),
+                line: self.nodes[last].line,
+                prev: 0,
+                next: after,
+            });
+            self.nodes[after].prev = self.nodes.len() - 1;
+            self.nodes.len() - 1
+        };
+        self.nodes[enter].next = leave;
+        self.nodes[leave].prev = enter;
+        Ok(Some((enter, leave)))
+    }
+
+    //Replaces a region with no area, lying along line, by its one or two distinct corners
+    fn flatten(&mut self, start: usize, line: Line) -> Result<usize, Overflow> {
+        let mut corners = vec![self.nodes[start].v];
+        let mut k = self.next(start);
+        while k != start {
+            corners.push(self.nodes[k].v);
+            k = self.next(k);
+        }
+        //Along a line, the ends are the least and greatest points in (x, y) order
+        let (mut lo, mut hi) = (corners[0], corners[0]);
+        for v in corners {
+            if v.cmp_xy(&lo)? == Ordering::Less {
+                lo = v;
+            }
+            if v.cmp_xy(&hi)? == Ordering::Greater {
+                hi = v;
+            }
+        }
+        let first = self.nodes.len();
+        let last = if lo == hi { first } else { first + 1 };
+        for (k, v) in [lo, hi].into_iter().take(last - first + 1).enumerate() {
+            self.nodes.push(Node {
+                v,
+                line,
+                prev: if k == 0 { last } else { first },
+                next: if k == 0 { last } else { first },
+            });
+        }
+        Ok(first)
+    }
+}
+
+impl SimplePolygon {
+    //Region that sees the whole polygon, as a convex polygon that may shrink to a segment or a
+    //single point, or None when the polygon is not star-shaped. Lee and Preparata's method in
+    //O(n): the bounding box is cut by the half-plane left of every edge in boundary order,
+    //keeping the two corners f and l where the lines of sight from the current vertex touch the
+    //region. Any part of the region beyond the next edge's line shows at f or l, so each cut
+    //starts there, and both only ever move forwards as the vertex walks round the region. A
+    //boundary that winds round the region more than once cannot be star-shaped, so walks that
+    //go on too long end it. Works in either orientation, and is exact for coordinates up to 2^19
+    //in absolute value, beyond which it gives KernelError::CoordinatesTooLarge
+    pub fn kernel(&self) -> Result<Option<RationalPolygon>, KernelError> {
+        let pl = self.get_point_list();
+        if pl
+            .iter()
+            .any(|p| p.x.unsigned_abs().max(p.y.unsigned_abs()) as i128 > MAX_COORD)
+        {
+            return Err(KernelError::CoordinatesTooLarge);
+        }
+        Ok(self.kernel_within_bounds()?)
+    }
+
+    //The kernel, once the coordinates are known to be small enough
+    fn kernel_within_bounds(&self) -> Result<Option<RationalPolygon>, Overflow> {
+        let pl = self.get_point_list();
+        let n = pl.len();
+        let (Some(x0), Some(x1), Some(y0), Some(y1)) = (
+            pl.iter().map(|p| p.x).min(),
+            pl.iter().map(|p| p.x).max(),
+            pl.iter().map(|p| p.y).min(),
+            pl.iter().map(|p| p.y).max(),
+        ) else {
+            return Ok(None);
+        };
+        //Start from the lowest of the leftmost vertices, which lies on the box
+        let s = (0..n).min_by_key(|&i| (pl[i].x, pl[i].y)).unwrap();
+        let clockwise = is_clockwise(pl);
+        let at = |k: usize| {
+            if clockwise {
+                &pl[(s + n - k % n) % n]
+            } else {
+                &pl[(s + k) % n]
+            }
+        };
+
+        let corners = [
+            Point::new(x0, y0),
+            Point::new(x1, y0),
+            Point::new(x1, y1),
+            Point::new(x0, y1),
+        ];
+        let mut nodes = Vec::new();
+        for i in 0..4 {
+            nodes.push(Node {
+                v: Vertex::from_point(&corners[i]),
+                line: Line::through_points(&corners[i], &corners[(i + 1) % 4])?,
+                prev: (i + 3) % 4,
+                next: (i + 1) % 4,
+            });
+        }
+        let mut region = Region { nodes };
+        //Vertices are in lowest terms, so one at a lattice point equals that point's
+        let is = |v: &Vertex, p: &Point| *v == Vertex::from_point(p);
+        //The first vertex is either a corner of the box or on its left side
+        let (mut f, mut l) = match (0..4).find(|&k| is(&region.nodes[k].v, at(0))) {
+            Some(k) => ((k + 1) % 4, (k + 3) % 4),
+            None => (0, 3),
+        };
+        let mut flat: Option<Line> = None;
+        let mut steps = 0;
+
+        for k in 0..n {
+            let (v, w) = (at(k), at(k + 1));
+            let cut = Line::through_points(v, w)?;
+            let side = |region: &Region, k: usize| cut.side(&region.nodes[k].v);
+            if let Some(along) = flat {
+                //The region is a segment or a point, so look at all of it
+                let g = region.next(f);
+                let t = if side(&region, f)? < 0 {
+                    f
+                } else if side(&region, g)? < 0 {
+                    g
+                } else {
+                    continue;
+                };
+                let Some((enter, _)) = region.cut(&cut, t)? else {
+                    return Ok(None);
+                };
+                f = region.flatten(enter, along)?;
+                continue;
+            }
+
+            let (sf, sl) = (side(&region, f)?, side(&region, l)?);
+            if sf < 0 || sl < 0 {
+                let Some((enter, leave)) = region.cut(&cut, if sf < 0 { f } else { l })? else {
+                    return Ok(None);
+                };
+                if region.next(leave) == enter {
+                    //Only the new side is left
+                    flat = Some(cut);
+                    f = region.flatten(enter, cut)?;
+                    continue;
+                }
+                //The new side is where the lost corner's line of sight now touches
+                if sf < 0 {
+                    f = enter;
+                } else {
+                    l = enter;
+                }
+            }
+
+            //Move the lines of sight to w. f stops where the region turns away from w, and l
+            //where it starts to face it
+            loop {
+                let next = region.next(f);
+                let (a, b) = (&region.nodes[f].v, &region.nodes[next].v);
+                let (c, d) = turn(w, a, b)?;
+                if !(c < 0 || (c == 0 && (is(a, w) || is(b, w) || d < 0 || is_nearer(w, a, b)?))) {
+                    break;
+                }
+                f = next;
+                steps += 1;
+                if steps > 4 * (region.nodes.len() + n) {
+                    return Ok(None);
+                }
+            }
+            loop {
+                let next = region.next(l);
+                let (c, d) = turn(w, &region.nodes[l].v, &region.nodes[next].v)?;
+                if !(c > 0 || (c == 0 && d > 0)) {
+                    break;
+                }
+                l = next;
+                steps += 1;
+                if steps > 4 * (region.nodes.len() + n) {
+                    return Ok(None);
+                }
+            }
+            if is(&region.nodes[l].v, w) {
+                l = region.prev(l);
+            }
+        }
+
+        let mut r = vec![region.nodes[f].v];
+        let mut k = region.next(f);
+        while k != f {
+            if r.last() != Some(&region.nodes[k].v) {
+                r.push(region.nodes[k].v);
+            }
+            k = region.next(k);
+        }
+        while r.len() > 1 && r.first() == r.last() {
+            r.pop();
+        }
+        Ok(Some(RationalPolygon::from_point_list(
+            r.into_iter().map(Vertex::to_rational).collect(),
+        )))
+    }
+
+    pub fn is_star_shaped(&self) -> Result<bool, KernelError> {
+        Ok(self.kernel()?.is_some())
+    }
+
+    //Whether a single guard standing at q sees the whole polygon, in O(n) and either orientation
+    pub fn is_in_kernel(&self, q: &Point) -> bool {
+        let pl = self.get_point_list();
+        let n = pl.len();
+        let sign = if is_clockwise(pl) { -1 } else { 1 };
+        (0..n).all(|i| Point::cross(&pl[i], &pl[(i + 1) % n], q) * sign >= 0)
+    }
+}
+
+#[cfg(test)]
+mod kernel_tests {
+    use super::*;
+    use crate::fixtures::notched_square;
+    use crate::primitives::{Rational, RationalPoint};
+    #[test]
+    fn test_kernel() {
+        let polygon = |v: &[(isize, isize)]| {
+            SimplePolygon::from_point_list(v.iter().map(|&(x, y)| Point::new(x, y)).collect())
+        };
+        let rational = |v: &[(isize, isize)]| -> Vec<RationalPoint> {
+            v.iter()
+                .map(|&(x, y)| RationalPoint::from_point(&Point::new(x, y)))
+                .collect()
+        };
+
+        //An L shape sees everything from its corner square
+        let l = polygon(&[(0, 0), (10, 0), (10, 4), (4, 4), (4, 10), (0, 10)]);
+        let k = l.kernel().unwrap().unwrap();
+        let mut got = k.get_point_list().clone();
+        got.sort_by_key(|p| (p.x, p.y));
+        assert_eq!(got, rational(&[(0, 0), (0, 4), (4, 0), (4, 4)]));
+        assert!(l.is_in_kernel(&Point::new(4, 4)));
+        assert!(!l.is_in_kernel(&Point::new(5, 1)));
+
+        //Seen from the bottom edge, the dent in the top hides all but a triangle
+        let dent = polygon(&[(0, 0), (6, 0), (6, 6), (3, 2), (0, 6)]);
+        let mut got = dent.kernel().unwrap().unwrap().get_point_list().clone();
+        got.sort_by_key(|p| (p.x, p.y));
+        let half = |n| Rational::new(n, 2);
+        assert_eq!(
+            got,
+            vec![
+                RationalPoint::new(half(3), half(0)),
+                RationalPoint::new(half(6), half(4)),
+                RationalPoint::new(half(9), half(0)),
+            ]
+        );
+
+        //Listed clockwise, the same L has the same kernel
+        let mut cw = l.get_point_list().clone();
+        cw.reverse();
+        let mut got = SimplePolygon::from_point_list(cw)
+            .kernel()
+            .unwrap()
+            .unwrap()
+            .get_point_list()
+            .clone();
+        got.sort_by_key(|p| (p.x, p.y));
+        assert_eq!(got, rational(&[(0, 0), (0, 4), (4, 0), (4, 4)]));
+
+        //Notches from above and below put two edges on x = 2 facing opposite ways
+        let pinched = polygon(&[
+            (0, 0),
+            (2, 0),
+            (2, 1),
+            (3, 0),
+            (4, 0),
+            (4, 4),
+            (2, 4),
+            (2, 3),
+            (1, 4),
+            (0, 4),
+        ]);
+        let mut got = pinched.kernel().unwrap().unwrap().get_point_list().clone();
+        got.sort_by_key(|p| (p.x, p.y));
+        assert_eq!(got, rational(&[(2, 1), (2, 3)]));
+        assert!(pinched.is_in_kernel(&Point::new(2, 2)));
+
+        //A notch cut down from the top hides each side from the other
+        let notch = notched_square();
+        assert_eq!(notch.kernel(), Ok(None));
+        assert_eq!(notch.is_star_shaped(), Ok(false));
+
+        let m = 1 << 19;
+        let big = polygon(&[(-m, -m), (m, -m), (m, m), (-m, m)]);
+        assert_eq!(big.kernel().unwrap().unwrap().get_point_list().len(), 4);
+        let bigger = polygon(&[(-m, -m), (m + 1, -m), (m, m), (-m, m)]);
+        assert_eq!(bigger.kernel(), Err(KernelError::CoordinatesTooLarge));
+    }
+}
@@ -0,0 +1,24 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: April 14, 2022
+// lib.rs - Library root exposing the geometry modules
+pub mod concave;
+pub mod dcel;
+pub mod dual;
+pub mod exterior;
+#[cfg(test)]
+mod fixtures;
+pub mod generate;
+pub mod guard;
+mod homogeneous;
+pub mod hull;
+pub mod kernel;
+pub mod location;
+pub mod mass;
+pub mod overlay;
+pub mod path;
+pub mod polygon;
+pub mod polygonize;
+pub mod primitives;
+pub mod ray;
+pub mod sample;
+pub mod visibility;
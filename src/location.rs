@@ -0,0 +1,213 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// location.rs - Point in polygon tests and point location over the faces of a DCEL
+use crate::dcel::{DCELFaceKey, DCEL};
+use crate::polygon::SimplePolygon;
+use crate::primitives::{DirEdge, Point};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+//Times the closed chain of points winds counterclockwise around q, counting upward crossings of
+//the ray from q towards +x against downward ones
+fn winding_number(pl: &[Point], q: &Point) -> isize {
+    let n = pl.len();
+    let mut w = 0;
+    for i in 0..n {
+        let (a, b) = (&pl[i], &pl[(i + 1) % n]);
+        if a.y <= q.y {
+            if b.y > q.y && Point::cross(a, b, q) > 0 {
+                w += 1;
+            }
+        } else if b.y <= q.y && Point::cross(a, b, q) < 0 {
+            w -= 1;
+        }
+    }
+    w
+}
+
+pub(crate) fn classify(pl: &[Point], q: &Point) -> Containment {
+    let n = pl.len();
+    if (0..n).any(|i| DirEdge::from_points(&pl[i], &pl[(i + 1) % n]).contains(q)) {
+        Containment::OnBoundary
+    } else if winding_number(pl, q) != 0 {
+        Containment::Inside
+    } else {
+        Containment::Outside
+    }
+}
+
+pub(crate) fn strictly_inside(pl: &[Point], q: &Point) -> bool {
+    classify(pl, q) == Containment::Inside
+}
+
+impl SimplePolygon {
+    //1 for points inside a counterclockwise polygon, -1 inside a clockwise one and 0 outside.
+    //Points on the boundary get whichever side the crossing rules put them on, so use
+    //classify_point when that matters
+    pub fn winding_number(&self, q: &Point) -> isize {
+        winding_number(self.get_point_list(), q)
+    }
+
+    pub fn classify_point(&self, q: &Point) -> Containment {
+        classify(self.get_point_list(), q)
+    }
+
+    pub fn contains(&self, q: &Point) -> bool {
+        self.classify_point(q) != Containment::Outside
+    }
+}
+
+//Segment crossing a slab, kept left to right with the face that lies above it
+#[derive(Debug, Clone)]
+struct SlabEdge {
+    a: Point,
+    b: Point,
+    above: Option<DCELFaceKey>,
+}
+
+//Point location by vertical slabs. The vertex x coordinates cut the plane into slabs, and no
+//two edges cross inside a slab, so each slab keeps the edges spanning it sorted bottom to top.
+//A query is two binary searches, O(log n), at the cost of O(n^2) space in the worst case
+#[derive(Debug)]
+pub struct SlabLocator {
+    xs: Vec<isize>,
+    slabs: Vec<Vec<SlabEdge>>,
+}
+
+impl SlabLocator {
+    //Face keys returned are only meaningful while the DCEL is left unchanged
+    pub fn from_dcel(dcel: &DCEL) -> Self {
+        let external = dcel.get_external_face().get_key();
+        let mut xs: Vec<isize> = dcel.get_vertex_list().iter().map(|p| p.x).collect();
+        xs.sort_unstable();
+        xs.dedup();
+
+        let mut slabs = vec![Vec::new(); xs.len().saturating_sub(1)];
+        for (e, a, b) in dcel.get_edge_list() {
+            if a.x == b.x {
+                continue;
+            }
+            //The half edge runs left to right, so its face is the one above
+            let face = dcel.get_edge_face(e);
+            let above = if face == external { None } else { Some(face) };
+            let first = xs.binary_search(&a.x).unwrap();
+            let last = xs.binary_search(&b.x).unwrap();
+            for slab in &mut slabs[first..last] {
+                slab.push(SlabEdge {
+                    a: a.clone(),
+                    b: b.clone(),
+                    above,
+                });
+            }
+        }
+        for (i, slab) in slabs.iter_mut().enumerate() {
+            let mid = xs[i] as i128 + xs[i + 1] as i128;
+            slab.sort_by(|e, f| compare_at(e, f, mid));
+        }
+        Self { xs, slabs }
+    }
+
+    //Internal face containing q, or None outside them all. A point on an edge is reported in
+    //the face above it, and one on a vertical edge in the face to its right
+    pub fn locate(&self, q: &Point) -> Option<DCELFaceKey> {
+        if self.slabs.is_empty() || q.x < self.xs[0] || q.x > *self.xs.last().unwrap() {
+            return None;
+        }
+        let i = (self.xs.partition_point(|&x| x <= q.x) - 1).min(self.slabs.len() - 1);
+        let slab = &self.slabs[i];
+        let below = slab.partition_point(|e| Point::cross(&e.a, &e.b, q) >= 0);
+        if below == 0 {
+            None
+        } else {
+            slab[below - 1].above
+        }
+    }
+}
+
+//Orders two segments spanning a slab by their height at x = mid / 2
+fn compare_at(e: &SlabEdge, f: &SlabEdge, mid: i128) -> Ordering {
+    //y(mid / 2) = num / den with a positive den
+    let height = |s: &SlabEdge| {
+        let (dx, dy) = ((s.b.x - s.a.x) as i128, (s.b.y - s.a.y) as i128);
+        (
+            2 * s.a.y as i128 * dx + (mid - 2 * s.a.x as i128) * dy,
+            2 * dx,
+        )
+    };
+    let ((p, q), (r, s)) = (height(e), height(f));
+    (p * s).cmp(&(r * q))
+}
+
+#[cfg(test)]
+mod location_tests {
+    use super::*;
+    use crate::fixtures::{notched_square, points};
+    use std::ops::RangeInclusive;
+    #[test]
+    fn test_classify_point() {
+        let p = notched_square();
+        let pl = p.get_point_list().clone();
+        assert_eq!(p.classify_point(&Point::new(2, 8)), Containment::Inside);
+        assert_eq!(p.classify_point(&Point::new(5, 8)), Containment::Outside);
+        assert_eq!(p.classify_point(&Point::new(5, 4)), Containment::OnBoundary);
+        assert_eq!(
+            p.classify_point(&Point::new(6, 10)),
+            Containment::OnBoundary
+        );
+        assert_eq!(p.classify_point(&Point::new(11, 4)), Containment::Outside);
+        assert!(p.contains(&Point::new(0, 5)));
+        assert_eq!(p.winding_number(&Point::new(5, 2)), 1);
+
+        let reversed = SimplePolygon::from_point_list(pl.into_iter().rev().collect());
+        assert_eq!(reversed.winding_number(&Point::new(5, 2)), -1);
+        assert_eq!(reversed.winding_number(&Point::new(5, 6)), 0);
+    }
+    //Every lattice point of the square range on both axes is located in a triangle that holds it
+    //when it is inside the polygon, and in none when it is outside
+    fn assert_locator_agrees(p: &SimplePolygon, range: RangeInclusive<isize>) {
+        let dcel = p.triangulate();
+        let locator = SlabLocator::from_dcel(&dcel);
+        for x in range.clone() {
+            for y in range.clone() {
+                let q = Point::new(x, y);
+                match (p.classify_point(&q), locator.locate(&q)) {
+                    (Containment::Inside, Some(f)) => {
+                        assert_ne!(classify(&dcel.get_face_points(f), &q), Containment::Outside)
+                    }
+                    (Containment::Outside, None) | (Containment::OnBoundary, _) => {}
+                    (c, f) => panic!("{:?} is {:?} but was located in {:?}", q, c, f),
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_slab_locator() {
+        let p = notched_square();
+        assert_locator_agrees(&p, -1..=11);
+    }
+    #[test]
+    fn test_slab_locator_staircase() {
+        //Vertical edges and vertices sharing their x make slabs with no room to spare
+        let p = SimplePolygon::from_point_list(points(&[
+            (0, 0),
+            (8, 0),
+            (8, 2),
+            (6, 2),
+            (6, 4),
+            (4, 4),
+            (4, 6),
+            (2, 6),
+            (2, 8),
+            (0, 8),
+        ]));
+        assert_eq!(p.classify_point(&Point::new(6, 3)), Containment::OnBoundary);
+        assert_eq!(p.classify_point(&Point::new(7, 3)), Containment::Outside);
+        assert_locator_agrees(&p, -1..=9);
+    }
+}
@@ -1,25 +1,27 @@
 // GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
 // Date: April 14, 2022
 // main.rs - The main runner code
-use dcel::DCEL;
 use draw::{Color, Colors};
-use polygon::SimplePolygon;
-use primitives::DirEdge;
-use std::collections::HashMap;
+use polygon_triangulation::dcel::{Colour, DiagonalOutcome, DCEL};
+use polygon_triangulation::polygon::SimplePolygon;
+use polygon_triangulation::primitives::{DirEdge, Point};
 use std::env;
 use std::sync::mpsc;
 use std::thread;
 
-mod dcel;
 mod draw;
-mod polygon;
-mod primitives;
 
+//Usage: polygon-triangulation <vertex count> [seed]. The polygons drawn use seed, seed + 1 and so
+//on, and each one's seed is printed first, so passing it back reproduces that polygon
 fn main() {
     let args: Vec<String> = env::args().collect();
-    assert_eq!(args.len(), 2);
+    assert!(args.len() == 2 || args.len() == 3);
+    let seed = match args.get(2) {
+        Some(s) => s.parse().unwrap(),
+        None => rand::random(),
+    };
     let (tx, rx) = mpsc::channel();
-    thread::spawn(move || worker(tx, args[1].parse().unwrap()));
+    thread::spawn(move || worker(tx, args[1].parse().unwrap(), seed));
     draw::drawer(rx);
 }
 
@@ -34,13 +36,13 @@ fn draw_polygon(
     pc: Option<Color>,
 ) {
     let point_list = polygon.get_point_list();
-    if lc.is_some() {
+    if let Some(lc) = lc {
         for idx in 0..point_list.len() {
             let next_idx = (idx + 1) % point_list.len();
 
             let msg = draw::DrawMessage::Edge(
                 (point_list[idx].clone(), point_list[next_idx].clone()),
-                lc.unwrap(),
+                lc,
             );
             tx.send(msg).unwrap();
         }
@@ -60,9 +62,18 @@ fn draw_edge(tx: &mpsc::Sender<draw::DrawMessage>, e: &DirEdge, c: Color) {
     tx.send(msg).unwrap();
 }
 
-fn worker(tx: mpsc::Sender<draw::DrawMessage>, arg: usize) {
-    let engine = |sleep_time| {
-        let original_p = SimplePolygon::gen_rand_hard(arg, 1000, 100).unwrap();
+fn check_diagonals(outcomes: &[DiagonalOutcome]) {
+    for o in outcomes {
+        if *o != DiagonalOutcome::Inserted {
+            panic!("Diagonal rejected: {:?}. Possible program bug", o);
+        }
+    }
+}
+
+fn worker(tx: mpsc::Sender<draw::DrawMessage>, arg: usize, seed: u64) {
+    let engine = |sleep_time, seed| {
+        println!("Polygon seed: {}", seed);
+        let original_p = SimplePolygon::gen_rand_hard_seeded(arg, 1000, 100, seed).unwrap();
 
         clear(&tx);
         draw_polygon(&tx, &original_p, Some(Colors::RED), Some(Colors::GREEN));
@@ -75,7 +86,7 @@ fn worker(tx: mpsc::Sender<draw::DrawMessage>, arg: usize) {
         thread::sleep(std::time::Duration::from_millis(sleep_time));
 
         let mut dcel = DCEL::from_simple_polygon(&original_p);
-        dcel.add_internal_diagonals(&monpart);
+        check_diagonals(&dcel.add_internal_diagonals(&monpart));
 
         let mut trg_diagonals = Vec::new();
         for face in dcel.get_internal_faces() {
@@ -91,7 +102,7 @@ fn worker(tx: mpsc::Sender<draw::DrawMessage>, arg: usize) {
         thread::sleep(std::time::Duration::from_millis(sleep_time));
 
         clear(&tx);
-        dcel.add_internal_diagonals(&trg_diagonals);
+        check_diagonals(&dcel.add_internal_diagonals(&trg_diagonals));
         for f in dcel.get_internal_faces() {
             draw_polygon(
                 &tx,
@@ -103,8 +114,18 @@ fn worker(tx: mpsc::Sender<draw::DrawMessage>, arg: usize) {
 
         clear(&tx);
         draw_polygon(&tx, &original_p, None, Some(Colors::GREEN));
-        for e in dcel.dual_graph() {
-            draw_edge(&tx, &e, Colors::BLUE);
+        let dual = dcel.dual_tree();
+        let centroid = |n| {
+            let (x, y) = dual.get_float_centroid(n);
+            Point::new(x.round() as isize, y.round() as isize)
+        };
+        for e in dual.get_edges() {
+            let (a, b) = e.nodes;
+            draw_edge(
+                &tx,
+                &DirEdge::from_points(&centroid(a), &centroid(b)),
+                Colors::BLUE,
+            );
         }
         thread::sleep(std::time::Duration::from_millis(sleep_time));
 
@@ -114,47 +135,43 @@ fn worker(tx: mpsc::Sender<draw::DrawMessage>, arg: usize) {
                 &tx,
                 &SimplePolygon::from_point_list(dcel.get_point_list(f)),
                 Some(Colors::YELLOW),
-                Some(Colors::GREEN),
+                None,
             );
         }
 
+        //Every vertex gets one of the three colours, drawn as its name says
         let color_map = dcel.three_color();
-        let mut color_freqs: HashMap<usize, usize> = HashMap::new();
-        for (p, i) in &color_map {
-            let c = match i {
-                1 => {
-                    *color_freqs.entry(1).or_insert(0) += 1;
-                    Colors::CYAN
-                }
-                2 => {
-                    *color_freqs.entry(2).or_insert(0) += 1;
-                    Colors::ORANGE
-                }
-                3 => {
-                    *color_freqs.entry(3).or_insert(0) += 1;
-                    Colors::PURPLE
-                }
-                _ => {
-                    panic!("Unknown color. Possible program bug");
-                }
+        assert!(dcel.verify_colouring(&color_map).is_empty());
+        let vertices = dcel.get_vertex_list();
+        for (&i, &clr) in &color_map {
+            let c = match clr {
+                Colour::Red => Colors::RED,
+                Colour::Green => Colors::GREEN,
+                Colour::Blue => Colors::BLUE,
             };
 
-            let msg = draw::DrawMessage::Point(p.clone(), c);
+            let msg = draw::DrawMessage::Point(vertices[i].clone(), c);
             tx.send(msg).unwrap();
         }
-        let (&min_clr, _) = color_freqs.iter().min_by_key(|(_, v)| *v).unwrap();
         thread::sleep(std::time::Duration::from_millis(sleep_time));
         clear(&tx);
         draw_polygon(&tx, &original_p, Some(Colors::RED), None);
-        for (p, &i) in &color_map {
-            if i == min_clr {
-                let msg = draw::DrawMessage::Point(p.clone(), Colors::INDIGO);
-                tx.send(msg).unwrap();
-            }
+        let guards = original_p.guard_set().guards;
+        for &i in &guards {
+            let msg = draw::DrawMessage::Point(vertices[i].clone(), Colors::INDIGO);
+            tx.send(msg).unwrap();
+        }
+        thread::sleep(std::time::Duration::from_millis(sleep_time));
+        let pruned = original_p
+            .prune_guards(&guards)
+            .expect("Fisk guards do not cover the polygon. Possible program bug");
+        for &i in &pruned.guards {
+            let msg = draw::DrawMessage::Point(vertices[i].clone(), Colors::PINK);
+            tx.send(msg).unwrap();
         }
     };
-    loop {
-        engine(3000);
+    for i in 0.. {
+        engine(3000, seed.wrapping_add(i));
         thread::sleep(std::time::Duration::from_millis(2000));
         tx.send(draw::DrawMessage::Clear(Colors::BLACK, Colors::CYAN, 10))
             .unwrap();
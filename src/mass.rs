@@ -0,0 +1,212 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// mass.rs - Area, centroid, perimeter and second moments of polygons and DCEL faces
+use crate::dcel::{DCELFace, DCEL};
+use crate::polygon::SimplePolygon;
+use crate::primitives::{Point, Rational, RationalPoint};
+
+//Second moments of area, ixx = integral of y^2, iyy = integral of x^2 and ixy = integral of x y
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AreaMoments {
+    pub ixx: Rational,
+    pub iyy: Rational,
+    pub ixy: Rational,
+}
+
+impl AreaMoments {
+    //Polar moment ixx + iyy, about the same point
+    pub fn get_polar(&self) -> Rational {
+        let (a, b) = (self.ixx, self.iyy);
+        Rational::new(
+            a.get_num() * b.get_den() + b.get_num() * a.get_den(),
+            a.get_den() * b.get_den(),
+        )
+    }
+}
+
+//Section properties of a region. The centroid and centroidal moments are None when the area is
+//zero. Moments are taken with the region's own orientation ignored, so they come out positive
+#[derive(Debug, Clone, PartialEq)]
+pub struct MassProperties {
+    pub signed_area: Rational,
+    pub area: Rational,
+    pub perimeter: f64,
+    pub centroid: Option<RationalPoint>,
+    //About the origin
+    pub moments: AreaMoments,
+    //About axes through the centroid, parallel to the coordinate axes
+    pub centroidal_moments: Option<AreaMoments>,
+}
+
+//Green's theorem sums over the edges of one or more boundary cycles. Each term is a multiple of
+//the cross product of the edge ends, so all of them flip sign with the orientation
+#[derive(Debug, Default)]
+struct Sums {
+    //Twice the signed area
+    a2: i128,
+    //6 times the first moments
+    sx: i128,
+    sy: i128,
+    //12 times ixx and iyy, 24 times ixy
+    ixx: i128,
+    iyy: i128,
+    ixy: i128,
+    perimeter: f64,
+}
+
+impl Sums {
+    fn add_cycle(&mut self, pl: &[Point]) {
+        for (i, p) in pl.iter().enumerate() {
+            let q = &pl[(i + 1) % pl.len()];
+            let (x0, y0, x1, y1) = (p.x as i128, p.y as i128, q.x as i128, q.y as i128);
+            let c = x0 * y1 - x1 * y0;
+            self.a2 += c;
+            self.sx += (x0 + x1) * c;
+            self.sy += (y0 + y1) * c;
+            self.ixx += (y0 * y0 + y0 * y1 + y1 * y1) * c;
+            self.iyy += (x0 * x0 + x0 * x1 + x1 * x1) * c;
+            self.ixy += (x0 * y1 + 2 * x0 * y0 + 2 * x1 * y1 + x1 * y0) * c;
+            self.perimeter += ((x1 - x0) as f64).hypot((y1 - y0) as f64);
+        }
+    }
+
+    fn finish(self) -> MassProperties {
+        let signed_area = Rational::new(self.a2, 2);
+        let s = if self.a2 < 0 { -1 } else { 1 };
+        let (a2, sx, sy) = (s * self.a2, s * self.sx, s * self.sy);
+        let (ixx, iyy, ixy) = (s * self.ixx, s * self.iyy, s * self.ixy);
+        let moments = AreaMoments {
+            ixx: Rational::new(ixx, 12),
+            iyy: Rational::new(iyy, 12),
+            ixy: Rational::new(ixy, 24),
+        };
+        let (centroid, centroidal_moments) = if a2 == 0 {
+            (None, None)
+        } else {
+            //Parallel axis theorem, I - A c^2 with A = a2 / 2 and c = sx / (3 a2)
+            let centroid = RationalPoint::new(Rational::new(sx, 3 * a2), Rational::new(sy, 3 * a2));
+            let centroidal = AreaMoments {
+                ixx: Rational::new(3 * a2 * ixx - 2 * sy * sy, 36 * a2),
+                iyy: Rational::new(3 * a2 * iyy - 2 * sx * sx, 36 * a2),
+                ixy: Rational::new(3 * a2 * ixy - 4 * sx * sy, 72 * a2),
+            };
+            (Some(centroid), Some(centroidal))
+        };
+        MassProperties {
+            signed_area,
+            area: Rational::new(a2, 2),
+            perimeter: self.perimeter,
+            centroid,
+            moments,
+            centroidal_moments,
+        }
+    }
+}
+
+impl SimplePolygon {
+    //All the figures at once, exact apart from the perimeter. Products grow as the fourth power
+    //of the coordinates, so these stay exact for coordinates up to about a million
+    pub fn mass_properties(&self) -> MassProperties {
+        let mut sums = Sums::default();
+        sums.add_cycle(self.get_point_list());
+        sums.finish()
+    }
+
+    //Positive for a counterclockwise polygon
+    pub fn signed_area(&self) -> Rational {
+        self.mass_properties().signed_area
+    }
+
+    pub fn area(&self) -> Rational {
+        self.mass_properties().area
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        self.mass_properties().perimeter
+    }
+
+    //None for a polygon of zero area, which has no centroid
+    pub fn centroid(&self) -> Option<RationalPoint> {
+        self.mass_properties().centroid
+    }
+
+    pub fn second_moments(&self) -> AreaMoments {
+        self.mass_properties().moments
+    }
+
+    pub fn centroidal_moments(&self) -> Option<AreaMoments> {
+        self.mass_properties().centroidal_moments
+    }
+}
+
+impl DCEL {
+    //Properties of an internal face with its holes taken out. The perimeter counts the hole
+    //boundaries too
+    pub fn get_face_mass_properties(&self, face: &DCELFace) -> MassProperties {
+        let mut sums = Sums::default();
+        sums.add_cycle(&self.get_point_list(face));
+        for hole in self.get_inner_point_lists(face) {
+            sums.add_cycle(&hole);
+        }
+        sums.finish()
+    }
+}
+
+#[cfg(test)]
+mod mass_tests {
+    use super::*;
+    use crate::fixtures::points;
+    use crate::primitives::DirEdge;
+    #[test]
+    fn test_mass_properties() {
+        let r = Rational::new;
+        //A 4 by 2 rectangle standing on the origin, listed clockwise
+        let rect = SimplePolygon::from_point_list(points(&[(0, 0), (0, 2), (4, 2), (4, 0)]));
+        assert_eq!(rect.signed_area(), r(-8, 1));
+        assert_eq!(rect.area(), r(8, 1));
+        assert_eq!(rect.perimeter(), 12.0);
+        assert_eq!(rect.centroid(), Some(RationalPoint::new(r(2, 1), r(1, 1))));
+        //b h^3 / 3, h b^3 / 3 and b^2 h^2 / 4 about the corner
+        let m = rect.second_moments();
+        assert_eq!((m.ixx, m.iyy, m.ixy), (r(32, 3), r(128, 3), r(16, 1)));
+        //b h^3 / 12 and h b^3 / 12 about the centre
+        let c = rect.centroidal_moments().unwrap();
+        assert_eq!((c.ixx, c.iyy, c.ixy), (r(8, 3), r(32, 3), r(0, 1)));
+        assert_eq!(c.get_polar(), r(40, 3));
+
+        //Collinear points enclose nothing, so there is no centroid to measure from
+        let flat = SimplePolygon::from_point_list(points(&[(0, 0), (2, 1), (4, 2)]));
+        assert_eq!(flat.area(), r(0, 1));
+        assert_eq!(flat.centroid(), None);
+        assert_eq!(flat.centroidal_moments(), None);
+    }
+    #[test]
+    fn test_face_mass_properties() {
+        //A hollow square section, 6 wide with a 2 wide hole in the middle
+        let square = |lo: isize, hi: isize| {
+            let c = [(lo, lo), (hi, lo), (hi, hi), (lo, hi)];
+            (0..4)
+                .map(|i| {
+                    let (p, q) = (c[i], c[(i + 1) % 4]);
+                    DirEdge::from_points(&Point::new(p.0, p.1), &Point::new(q.0, q.1))
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut segments = square(0, 6);
+        segments.append(&mut square(2, 4));
+        let dcel = DCEL::from_planar_graph(&segments, &[]).unwrap();
+        let faces = dcel.get_internal_faces();
+        let ring = faces
+            .iter()
+            .find(|f| !dcel.get_inner_point_lists(f).is_empty())
+            .unwrap();
+        let m = dcel.get_face_mass_properties(ring);
+        let r = Rational::new;
+        assert_eq!(m.area, r(32, 1));
+        assert_eq!(m.perimeter, 32.0);
+        assert_eq!(m.centroid, Some(RationalPoint::new(r(3, 1), r(3, 1))));
+        //(6^4 - 2^4) / 12 about either centroidal axis
+        let c = m.centroidal_moments.unwrap();
+        assert_eq!((c.ixx, c.iyy, c.ixy), (r(320, 3), r(320, 3), r(0, 1)));
+    }
+}
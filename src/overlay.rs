@@ -0,0 +1,541 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// overlay.rs - Map overlay of two subdivisions
+use crate::dcel::{DCELEdgeKey, DCELFaceKey, DCEL};
+use crate::primitives::{gcd, DirEdge, Point, PointOrientation};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+//Crossings have denominators below 8 MAX_COORD^2, and comparing the heights of two segments at a
+//crossing stays within i128 while every input coordinate is below this in absolute value
+const MAX_COORD: i128 = 1 << 23;
+//The overlay keeps its coordinates below this, where point location in a DCEL is still exact
+const MAX_SCALED: i128 = 1 << 40;
+
+//Lattice segment with its lexicographically smaller end first
+type PieceKey = ((isize, isize), (isize, isize));
+
+#[derive(Debug, PartialEq)]
+pub enum OverlayError {
+    //Some input coordinate is beyond MAX_COORD, or putting the crossings on the lattice would
+    //take the overlay beyond MAX_SCALED
+    CoordinatesTooLarge,
+}
+
+#[derive(Debug)]
+pub struct Overlay {
+    //In the input coordinates multiplied by scale
+    pub dcel: DCEL,
+    //Smallest factor that puts every crossing of the two inputs on the integer lattice
+    pub scale: isize,
+    //Faces of the first and of the second input that each face of dcel lies in
+    pub origins: HashMap<DCELFaceKey, (DCELFaceKey, DCELFaceKey)>,
+}
+
+//Edge of the first (0) or second (1) input, from its lexicographically smaller end p to q
+struct Segment {
+    set: usize,
+    edge: DCELEdgeKey,
+    p: Point,
+    q: Point,
+}
+
+impl Segment {
+    //Height where the segment meets the vertical line through at, times at.w, as a fraction
+    //with a positive denominator. A vertical segment is met at the event point itself
+    fn height_at(&self, at: &EventPoint) -> (i128, i128) {
+        let dx = (self.q.x - self.p.x) as i128;
+        if dx == 0 {
+            return (at.y, 1);
+        }
+        let dy = (self.q.y - self.p.y) as i128;
+        (
+            self.p.y as i128 * dx * at.w + (at.x - self.p.x as i128 * at.w) * dy,
+            dx,
+        )
+    }
+
+    //Slopes as (dy, dx), with vertical segments above every other
+    fn cmp_slope(&self, other: &Segment) -> Ordering {
+        let slope = |s: &Segment| ((s.q.y - s.p.y) as i128, (s.q.x - s.p.x) as i128);
+        match (slope(self), slope(other)) {
+            ((_, 0), (_, 0)) => Ordering::Equal,
+            ((_, 0), _) => Ordering::Greater,
+            (_, (_, 0)) => Ordering::Less,
+            ((dy1, dx1), (dy2, dx2)) => (dy1 * dx2).cmp(&(dy2 * dx1)),
+        }
+    }
+}
+
+//The point (x / w, y / w) in lowest terms with w positive. Ends of segments and isolated points
+//have w = 1, and crossings need not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EventPoint {
+    x: i128,
+    y: i128,
+    w: i128,
+}
+
+impl EventPoint {
+    fn new(x: i128, y: i128, w: i128) -> Self {
+        let g = gcd(gcd(x, y), w) * w.signum();
+        Self {
+            x: x / g,
+            y: y / g,
+            w: w / g,
+        }
+    }
+
+    fn from_point(p: &Point) -> Self {
+        Self::new(p.x as i128, p.y as i128, 1)
+    }
+
+    //Lattice point reached by multiplying by scale, which w has to divide
+    fn scaled(&self, scale: i128) -> (isize, isize) {
+        let k = scale / self.w;
+        ((self.x * k) as isize, (self.y * k) as isize)
+    }
+}
+
+//Left to right and then bottom to top
+impl Ord for EventPoint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.x * other.w)
+            .cmp(&(other.x * self.w))
+            .then_with(|| (self.y * other.w).cmp(&(other.y * self.w)))
+    }
+}
+
+impl PartialOrd for EventPoint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+//Event point of the sweep, and whether the segments through it have been passed yet. Events go
+//left to right and then bottom to top, as if the sweep line were turned a little clockwise, so
+//the part of the vertical line below the event point has already been swept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Sweep {
+    at: EventPoint,
+    after: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    //Just below or just above every segment through the event point
+    Below,
+    Segment(usize),
+    Above,
+}
+
+//Segment in the sweep status, ordered from the bottom up, with the sweep position it went in
+//at. Segments only change order at event points, where those through the point are taken out
+//just before it and put back just after, so two entries are compared where the later of them
+//went in, and keep that order for as long as both stay in
+struct Status<'a> {
+    sweep: Sweep,
+    segments: &'a [Segment],
+    entry: Entry,
+}
+
+impl PartialEq for Status<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Status<'_> {}
+
+impl PartialOrd for Status<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Status<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let sweep = self.sweep.max(other.sweep);
+        let at = &sweep.at;
+        let height = |e: Entry| match e {
+            Entry::Segment(i) => self.segments[i].height_at(at),
+            _ => (at.y, 1),
+        };
+        let ((n1, d1), (n2, d2)) = (height(self.entry), height(other.entry));
+        let by_height = (n1 * d2).cmp(&(n2 * d1));
+        if by_height.is_ne() {
+            return by_height;
+        }
+        let (i, j) = match (self.entry, other.entry) {
+            (Entry::Segment(i), Entry::Segment(j)) => (i, j),
+            (a, b) => {
+                let rank = |e: Entry| match e {
+                    Entry::Below => 0,
+                    Entry::Segment(_) => 1,
+                    Entry::Above => 2,
+                };
+                return rank(a).cmp(&rank(b));
+            }
+        };
+        //Both pass through the same point. Past it they are in order of slope, and before it in
+        //the reverse order
+        let passed = match n1.cmp(&(at.y * d1)) {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal => sweep.after,
+        };
+        let (a, b) = (&self.segments[i], &self.segments[j]);
+        let by_slope = if passed {
+            a.cmp_slope(b)
+        } else {
+            b.cmp_slope(a)
+        };
+        by_slope.then(i.cmp(&j))
+    }
+}
+
+//Crossing point of two segments that cross at a point interior to both
+fn proper_crossing(p1: &Point, q1: &Point, p2: &Point, q2: &Point) -> Option<EventPoint> {
+    let o1 = Point::orientation(p1, q1, p2);
+    let o2 = Point::orientation(p1, q1, q2);
+    let o3 = Point::orientation(p2, q2, p1);
+    let o4 = Point::orientation(p2, q2, q1);
+    if [&o1, &o2, &o3, &o4].contains(&&PointOrientation::Collinear) || o1 == o2 || o3 == o4 {
+        return None;
+    }
+    let (rx, ry) = ((q1.x - p1.x) as i128, (q1.y - p1.y) as i128);
+    let (sx, sy) = ((q2.x - p2.x) as i128, (q2.y - p2.y) as i128);
+    let (wx, wy) = ((p2.x - p1.x) as i128, (p2.y - p1.y) as i128);
+    let den = rx * sy - ry * sx;
+    let t = wx * sy - wy * sx;
+    Some(EventPoint::new(
+        p1.x as i128 * den + t * rx,
+        p1.y as i128 * den + t * ry,
+        den,
+    ))
+}
+
+//Overlays two subdivisions. Every segment of either input is cut at the points where it meets
+//the other input, and the pieces are assembled into a new DCEL. The meeting points are found by
+//a Bentley-Ottmann sweep, which only tests segments that become neighbours in the sweep status,
+//so this takes O((n + k) log n) for n segments meeting at k points. Crossings are exact
+//fractions, and the DCEL holds both inputs scaled by the smallest factor that puts all of them on
+//the lattice
+pub fn overlay(a: &DCEL, b: &DCEL) -> Result<Overlay, OverlayError> {
+    let inputs = [a, b];
+    let mut segments = Vec::new();
+    for (set, d) in inputs.iter().enumerate() {
+        for (edge, p, q) in d.get_edge_list() {
+            segments.push(Segment { set, edge, p, q });
+        }
+    }
+    let isolated: Vec<Point> = inputs
+        .iter()
+        .flat_map(|d| {
+            let mut r = d.get_isolated_points(d.get_external_face());
+            for f in d.get_internal_faces() {
+                r.append(&mut d.get_isolated_points(f));
+            }
+            r
+        })
+        .collect();
+    let too_large = |p: &Point| p.x.unsigned_abs().max(p.y.unsigned_abs()) as i128 > MAX_COORD;
+    if segments.iter().any(|s| too_large(&s.p) || too_large(&s.q)) || isolated.iter().any(too_large)
+    {
+        return Err(OverlayError::CoordinatesTooLarge);
+    }
+
+    //Segments starting at each event point. Ends and isolated points are events with none
+    let mut events: BTreeMap<EventPoint, Vec<usize>> = BTreeMap::new();
+    for (i, s) in segments.iter().enumerate() {
+        events
+            .entry(EventPoint::from_point(&s.p))
+            .or_default()
+            .push(i);
+        events.entry(EventPoint::from_point(&s.q)).or_default();
+    }
+    for p in &isolated {
+        events.entry(EventPoint::from_point(p)).or_default();
+    }
+
+    //Points inside each segment where it has to be cut
+    let mut cuts: Vec<Vec<EventPoint>> = vec![Vec::new(); segments.len()];
+    let mut status: BTreeSet<Status> = BTreeSet::new();
+    while let Some((at, starts)) = events.pop_first() {
+        let entry = |after: bool, entry: Entry| Status {
+            sweep: Sweep { at, after },
+            segments: &segments,
+            entry,
+        };
+        let through: Vec<usize> = status
+            .range(entry(false, Entry::Below)..entry(false, Entry::Above))
+            .map(|s| match s.entry {
+                Entry::Segment(i) => i,
+                _ => unreachable!(),
+            })
+            .collect();
+        for &i in &through {
+            status.remove(&entry(false, Entry::Segment(i)));
+        }
+        for &i in &through {
+            if EventPoint::from_point(&segments[i].q) != at {
+                cuts[i].push(at);
+                status.insert(entry(true, Entry::Segment(i)));
+            }
+        }
+        for &i in &starts {
+            status.insert(entry(true, Entry::Segment(i)));
+        }
+
+        //Segments that have just become neighbours may cross further on
+        let index = |s: Option<&Status>| match s.map(|s| s.entry) {
+            Some(Entry::Segment(i)) => Some(i),
+            _ => None,
+        };
+        let (below, above) = (entry(true, Entry::Below), entry(true, Entry::Above));
+        let under = index(status.range(..&below).next_back());
+        let over = index(status.range(&above..).next());
+        let mut on = status.range(&below..&above);
+        let pairs = match (index(on.next()), index(on.next_back())) {
+            (Some(lowest), highest) => [(under, Some(lowest)), (highest.or(Some(lowest)), over)],
+            (None, _) => [(under, over), (None, None)],
+        };
+        for (s, t) in pairs {
+            let (Some(s), Some(t)) = (s, t) else {
+                continue;
+            };
+            let (s, t) = (&segments[s], &segments[t]);
+            if let Some(c) = proper_crossing(&s.p, &s.q, &t.p, &t.q) {
+                if c > at {
+                    events.entry(c).or_default();
+                }
+            }
+        }
+    }
+
+    //Multiplying by the least common multiple of the denominators puts every cut on the lattice
+    let largest = segments
+        .iter()
+        .flat_map(|s| [&s.p, &s.q])
+        .chain(&isolated)
+        .map(|p| p.x.unsigned_abs().max(p.y.unsigned_abs()) as i128)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let mut scale: i128 = 1;
+    for c in cuts.iter().flatten() {
+        scale = scale / gcd(scale, c.w) * c.w;
+        if scale > MAX_SCALED / largest {
+            return Err(OverlayError::CoordinatesTooLarge);
+        }
+    }
+
+    //Pieces of the overlay, each with the half edge of either input that runs along it from its
+    //lexicographically smaller end
+    let mut pieces: HashMap<PieceKey, [Option<DCELEdgeKey>; 2]> = HashMap::new();
+    for (s, mut pts) in segments.iter().zip(cuts) {
+        pts.push(EventPoint::from_point(&s.p));
+        pts.push(EventPoint::from_point(&s.q));
+        pts.sort();
+        pts.dedup();
+        for w in pts.windows(2) {
+            let key = (w[0].scaled(scale), w[1].scaled(scale));
+            pieces.entry(key).or_default()[s.set] = Some(s.edge);
+        }
+    }
+
+    let vertices: HashSet<(isize, isize)> = pieces.keys().flat_map(|&(p, q)| [p, q]).collect();
+    let mut lone_points: Vec<Point> = isolated
+        .iter()
+        .map(|p| {
+            let (x, y) = EventPoint::from_point(p).scaled(scale);
+            Point::new(x, y)
+        })
+        .filter(|p| !vertices.contains(&(p.x, p.y)))
+        .collect();
+    lone_points.sort_by_key(|p| (p.x, p.y));
+    lone_points.dedup();
+
+    let segments: Vec<DirEdge> = pieces
+        .keys()
+        .map(|&((px, py), (qx, qy))| DirEdge::from_points(&Point::new(px, py), &Point::new(qx, qy)))
+        .collect();
+    let dcel = DCEL::from_planar_graph(&segments, &lone_points)
+        .expect("Overlay pieces do not form a planar graph. Possible program bug");
+
+    //A piece running along an edge of an input borders the same faces of it on the same sides,
+    //and across any other piece the face of that input stays the same. Every face of the overlay
+    //is reached that way from one with a piece of the input's edges on its boundary, unless the
+    //input has no edges and so only its external face
+    let mut faces: Vec<DCELFaceKey> = dcel
+        .get_internal_faces()
+        .iter()
+        .map(|f| f.get_key())
+        .collect();
+    faces.push(dcel.get_external_face().get_key());
+    let along = |e: DCELEdgeKey, set: usize| {
+        let (p, q) = dcel.get_edge_points(e);
+        let (p, q) = ((p.x, p.y), (q.x, q.y));
+        let src = pieces[&(p.min(q), p.max(q))][set]?;
+        Some(if p < q {
+            inputs[set].get_edge_face(src)
+        } else {
+            inputs[set].get_edge_face(inputs[set].get_twin_edge(src))
+        })
+    };
+    let mut found: [HashMap<DCELFaceKey, DCELFaceKey>; 2] = Default::default();
+    for (set, found) in found.iter_mut().enumerate() {
+        let mut queue = VecDeque::new();
+        for &f in &faces {
+            let cycles = dcel.get_face_cycles(f);
+            if let Some(g) = cycles.iter().flatten().find_map(|&e| along(e, set)) {
+                found.insert(f, g);
+                queue.push_back(f);
+            }
+        }
+        while let Some(f) = queue.pop_front() {
+            for e in dcel.get_face_cycles(f).into_iter().flatten() {
+                let across = dcel.get_edge_face(dcel.get_twin_edge(e));
+                if along(e, set).is_none() && !found.contains_key(&across) {
+                    found.insert(across, found[&f]);
+                    queue.push_back(across);
+                }
+            }
+        }
+    }
+    let origins = faces
+        .into_iter()
+        .map(|f| {
+            let origin = |set: usize| {
+                let ext = inputs[set].get_external_face().get_key();
+                found[set].get(&f).copied().unwrap_or(ext)
+            };
+            (f, (origin(0), origin(1)))
+        })
+        .collect();
+
+    Ok(Overlay {
+        dcel,
+        scale: scale as isize,
+        origins,
+    })
+}
+
+#[cfg(test)]
+mod overlay_tests {
+    use super::*;
+    use crate::polygon::SimplePolygon;
+    #[test]
+    fn test_overlay() {
+        let poly = |pts: &[(isize, isize)]| {
+            let pl = pts.iter().map(|&(x, y)| Point::new(x, y)).collect();
+            DCEL::from_simple_polygon(&SimplePolygon::from_point_list(pl))
+        };
+        let inside = |d: &DCEL| d.get_internal_faces()[0].get_key();
+        let outside = |d: &DCEL| d.get_external_face().get_key();
+        //Points of the face lying in both inputs, sorted. Each face here lies in a different
+        //pair of faces of the inputs
+        let common = |r: &Overlay, a: &DCEL, b: &DCEL| {
+            assert_eq!(r.dcel.validate(), vec![]);
+            let ext = r.dcel.get_external_face().get_key();
+            assert_eq!(r.origins[&ext], (outside(a), outside(b)));
+            let mut found = HashSet::new();
+            let mut pts = Vec::new();
+            for f in r.dcel.get_internal_faces() {
+                let (fa, fb) = r.origins[&f.get_key()];
+                found.insert((fa == inside(a), fb == inside(b)));
+                if fa == inside(a) && fb == inside(b) {
+                    pts = r
+                        .dcel
+                        .get_point_list(f)
+                        .iter()
+                        .map(|p| (p.x, p.y))
+                        .collect();
+                    pts.sort();
+                }
+            }
+            assert_eq!(found.len(), r.dcel.get_internal_faces().len());
+            pts
+        };
+        //The top of the square crosses the hypotenuse at (3/2, 1), which is a lattice point at
+        //twice the size
+        let a = poly(&[(0, 0), (3, 0), (0, 2)]);
+        let b = poly(&[(1, 0), (4, 0), (4, 1), (1, 1)]);
+        let r = overlay(&a, &b).unwrap();
+        assert_eq!(r.scale, 2);
+        assert_eq!(r.dcel.get_internal_faces().len(), 3);
+        assert_eq!(common(&r, &a, &b), vec![(2, 0), (2, 2), (3, 2), (6, 0)]);
+        let a = poly(&[(0, 0), (6, 0), (0, 4)]);
+        let b = poly(&[(2, 0), (8, 0), (8, 2), (2, 2)]);
+        let r = overlay(&a, &b).unwrap();
+        assert_eq!(r.scale, 1);
+        assert_eq!(common(&r, &a, &b), vec![(2, 0), (2, 2), (3, 2), (6, 0)]);
+
+        //The hypotenuse leaves the square through (1, 4/3) and (3/2, 1)
+        let a = poly(&[(0, 0), (3, 0), (0, 2)]);
+        let b = poly(&[(1, 1), (4, 1), (4, 4), (1, 4)]);
+        let r = overlay(&a, &b).unwrap();
+        assert_eq!(r.scale, 6);
+        assert_eq!(common(&r, &a, &b), vec![(6, 6), (6, 8), (9, 6)]);
+
+        //A triangle inside a pentagon meets none of its edges, and is reached through the hole
+        //it leaves
+        let a = poly(&[(1, 4), (0, 1), (1, 0), (4, 1), (4, 4)]);
+        let b = poly(&[(2, 3), (1, 1), (3, 3)]);
+        let r = overlay(&a, &b).unwrap();
+        assert_eq!(r.scale, 1);
+        assert_eq!(common(&r, &a, &b), vec![(1, 1), (2, 3), (3, 3)]);
+
+        let far = poly(&[(0, 0), (1 << 24, 0), (0, 1)]);
+        assert_eq!(
+            overlay(&a, &far).unwrap_err(),
+            OverlayError::CoordinatesTooLarge
+        );
+    }
+    #[test]
+    fn test_overlay_sweep() {
+        let poly = |pts: &[(isize, isize)]| {
+            let pl = pts.iter().map(|&(x, y)| Point::new(x, y)).collect();
+            DCEL::from_simple_polygon(&SimplePolygon::from_point_list(pl))
+        };
+        //A comb with four teeth going up, crossed by two bars joined on the right, so that the
+        //sweep meets 32 crossings between vertical and horizontal edges
+        let mut pts = vec![(0, 0), (16, 0), (16, 1)];
+        for k in (0..4).rev() {
+            pts.extend([
+                (4 * k + 3, 1),
+                (4 * k + 3, 12),
+                (4 * k + 1, 12),
+                (4 * k + 1, 1),
+            ]);
+        }
+        pts.push((0, 1));
+        let a = poly(&pts);
+        let b = poly(&[
+            (-1, 3),
+            (19, 3),
+            (19, 9),
+            (-1, 9),
+            (-1, 7),
+            (17, 7),
+            (17, 5),
+            (-1, 5),
+        ]);
+        let r = overlay(&a, &b).unwrap();
+        assert_eq!(r.dcel.validate(), vec![]);
+        let inside = |d: &DCEL| d.get_internal_faces()[0].get_key();
+        let both = r
+            .dcel
+            .get_internal_faces()
+            .iter()
+            .filter(|f| r.origins[&f.get_key()] == (inside(&a), inside(&b)))
+            .count();
+        assert_eq!(both, 8);
+
+        //Sharing part of the comb's base, which is cut where the two separate
+        let c = poly(&[(4, 0), (4, -3), (12, -3), (12, 0)]);
+        let r = overlay(&a, &c).unwrap();
+        assert_eq!(r.dcel.validate(), vec![]);
+        assert_eq!(r.dcel.get_internal_faces().len(), 2);
+    }
+}
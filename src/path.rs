@@ -0,0 +1,793 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// path.rs - Shortest paths inside a polygon
+use crate::dcel::DCEL;
+use crate::dual::DualTree;
+use crate::homogeneous::{det, hull, mul, Line, Overflow, Vertex};
+use crate::location::strictly_inside;
+use crate::polygon::SimplePolygon;
+use crate::primitives::{Point, RationalPoint};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPath {
+    //Starts at s and ends at t, bending only at reflex vertices of the polygon
+    pub points: Vec<Point>,
+    pub length: f64,
+}
+
+pub(crate) fn polyline_length(points: &[Point]) -> f64 {
+    points
+        .windows(2)
+        .map(|w| {
+            let (dx, dy) = ((w[1].x - w[0].x) as f64, (w[1].y - w[0].y) as f64);
+            dx.hypot(dy)
+        })
+        .sum()
+}
+
+//Funnel algorithm over portals given as (left, right) seen while walking from s to t. The first
+//and last portals are the points s and t themselves
+fn funnel(portals: &[(Point, Point)]) -> Vec<Point> {
+    let mut path = vec![portals[0].0.clone()];
+    //Apex and the two sides of the funnel, with the portals the sides came from
+    let (mut a, mut l, mut r) = (&portals[0].0, &portals[0].0, &portals[0].0);
+    let (mut left, mut right) = (0, 0);
+    let mut i = 1;
+    while i < portals.len() {
+        let (pl, pr) = (&portals[i].0, &portals[i].1);
+
+        //Narrow the right side unless it would swing past the left one
+        if Point::cross(a, r, pr) >= 0 {
+            if a == r || Point::cross(a, l, pr) < 0 {
+                (r, right) = (pr, i);
+            } else {
+                //The left side becomes the new apex, and the walk restarts from there
+                (a, r, right) = (l, l, left);
+                if path.last() != Some(a) {
+                    path.push(a.clone());
+                }
+                i = left + 1;
+                continue;
+            }
+        }
+        if Point::cross(a, l, pl) <= 0 {
+            if a == l || Point::cross(a, r, pl) > 0 {
+                (l, left) = (pl, i);
+            } else {
+                (a, l, left) = (r, r, right);
+                if path.last() != Some(a) {
+                    path.push(a.clone());
+                }
+                i = right + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    let t = &portals.last().unwrap().0;
+    if path.last() != Some(t) {
+        path.push(t.clone());
+    }
+    path
+}
+
+//Triangulation with its dual tree rooted at the triangle holding a source point
+struct RootedTriangulation {
+    dcel: DCEL,
+    dual: DualTree,
+    //Dual edge each node is entered through, coming from the root
+    via: Vec<Option<usize>>,
+    //Nodes in depth first preorder from the root
+    order: Vec<usize>,
+}
+
+impl RootedTriangulation {
+    fn new(polygon: &SimplePolygon, s: &Point) -> Self {
+        let dcel = polygon.triangulate();
+        let dual = DualTree::from_dcel(&dcel);
+        let mut r = RootedTriangulation {
+            dcel,
+            dual,
+            via: Vec::new(),
+            order: Vec::new(),
+        };
+        let root = r.locate(s);
+        r.via = vec![None; r.dual.get_node_count()];
+        for (node, e) in r.dual.dfs(root) {
+            r.via[node] = e;
+            r.order.push(node);
+        }
+        r
+    }
+
+    fn locate(&self, p: &Point) -> usize {
+        let f = self.dcel.get_face_containing(p.x as i128, p.y as i128, 1);
+        self.dual.get_node(f).unwrap()
+    }
+
+    //Diagonals crossed going from the root to node, as (left, right) portals
+    fn sleeve(&self, node: usize) -> Vec<(Point, Point)> {
+        let mut portals = Vec::new();
+        let mut node = node;
+        while let Some(e) = self.via[node] {
+            let de = &self.dual.get_edges()[e];
+            let (u, v) = self.dcel.get_edge_points(de.half_edge);
+            //The half-edge runs counterclockwise around nodes.0, so leaving that triangle
+            //through it has its end on the left
+            let (prev, portal) = if de.nodes.1 == node {
+                (de.nodes.0, (v, u))
+            } else {
+                (de.nodes.1, (u, v))
+            };
+            portals.push(portal);
+            node = prev;
+        }
+        portals.reverse();
+        portals
+    }
+
+    //Node the given node is entered from, and the diagonal crossed as (left, right) vertex indices
+    fn entry(&self, node: usize) -> Option<(usize, (usize, usize))> {
+        let de = &self.dual.get_edges()[self.via[node]?];
+        let (u, v) = self.dcel.get_edge_indices(de.half_edge);
+        Some(if de.nodes.1 == node {
+            (de.nodes.0, (v, u))
+        } else {
+            (de.nodes.1, (u, v))
+        })
+    }
+}
+
+//Portals walked through from s to t, starting and ending with the points themselves
+fn sleeve_portals(s: &Point, sleeve: Vec<(Point, Point)>, t: &Point) -> Vec<(Point, Point)> {
+    let mut portals = vec![(s.clone(), s.clone())];
+    portals.extend(sleeve);
+    portals.push((t.clone(), t.clone()));
+    //A point lying on a diagonal would leave the funnel with no width, but it already stands in
+    //that portal so the portal can be dropped
+    let on_portal = |p: &Point, (l, r): &(Point, Point)| {
+        Point::cross(l, r, p) == 0 && (p.x - l.x) * (p.x - r.x) + (p.y - l.y) * (p.y - r.y) < 0
+    };
+    if portals.len() > 2 && on_portal(s, &portals[1]) {
+        portals.remove(1);
+    }
+    if portals.len() > 2 && on_portal(t, &portals[portals.len() - 2]) {
+        portals.remove(portals.len() - 2);
+    }
+    portals
+}
+
+//Shortest path from s to t through the given sleeve of diagonals
+fn pull_taut(s: &Point, sleeve: Vec<(Point, Point)>, t: &Point) -> Vec<Point> {
+    funnel(&sleeve_portals(s, sleeve, t))
+}
+
+//Shortest path between two points strictly inside the polygon. The triangles holding s and t
+//are joined through the dual tree of a triangulation, and the funnel algorithm pulls the path
+//taut through the diagonals crossed on the way
+pub fn shortest_path(polygon: &SimplePolygon, s: &Point, t: &Point) -> Option<ShortestPath> {
+    let pl = polygon.get_point_list();
+    if !strictly_inside(pl, s) || !strictly_inside(pl, t) {
+        return None;
+    }
+    let rt = RootedTriangulation::new(polygon, s);
+    let points = pull_taut(s, rt.sleeve(rt.locate(t)), t);
+    let length = polyline_length(&points);
+    Some(ShortestPath { points, length })
+}
+
+//Shortest paths from one source to every vertex of a polygon, each path's last bend being the
+//vertex's parent in the tree. Vertices a path runs straight through are not bends
+pub struct ShortestPathTree {
+    source: Point,
+    point_list: Vec<Point>,
+    rooted: RootedTriangulation,
+    //None when the vertex sees the source directly
+    parents: Vec<Option<usize>>,
+    distances: Vec<f64>,
+}
+
+//First index in lo..hi at which g fails, or hi if it never does, for g that holds up to some
+//index and fails from there on. Probing outwards from both ends at once costs O(log) of the
+//distance from the answer to the nearer end
+fn first_failing(lo: usize, hi: usize, g: impl Fn(usize) -> bool) -> usize {
+    let (mut a, mut b) = (lo, hi);
+    let mut step = 1;
+    while b - a > step {
+        if !g(a + step - 1) {
+            b = a + step - 1;
+            break;
+        }
+        if g(b - step) {
+            a = b - step + 1;
+            break;
+        }
+        step *= 2;
+    }
+    while a < b {
+        let m = (a + b) / 2;
+        if g(m) {
+            a = m + 1;
+        } else {
+            b = m;
+        }
+    }
+    a
+}
+
+//Triangle on the walk in ShortestPathTree::new. Its funnel, the shortest paths to the ends of
+//the diagonal it was entered by, is buffer[lo..=hi] with its apex at apex
+struct FunnelFrame {
+    node: usize,
+    lo: usize,
+    apex: usize,
+    hi: usize,
+    //The vertex opposite the entry diagonal, and the funnel slot its path leaves from
+    opposite: usize,
+    tangent: usize,
+    //Length of the undo log before the funnel was set up
+    undo: usize,
+}
+
+impl ShortestPathTree {
+    //The funnel walk of Guibas, Hershberger, Leven, Sharir and Tarjan. Each triangle is entered
+    //with the funnel of shortest paths to the ends of its entry diagonal, and the vertex opposite
+    //is reached from where it touches the funnel. The funnels of the two diagonals leading on are
+    //the parts either side of that vertex, each closed off by the opposite vertex, so a child's
+    //funnel is its parent's with one slot overwritten. Triangles come in depth first preorder, so
+    //those slots are put back from an undo log on leaving a subtree. With the tangent found by
+    //searching from both ends of the funnel, the whole walk is O(n)
+    pub fn new(polygon: &SimplePolygon, s: &Point) -> Option<Self> {
+        let pl = polygon.get_point_list();
+        if !strictly_inside(pl, s) {
+            return None;
+        }
+        let rooted = RootedTriangulation::new(polygon, s);
+        let n = pl.len();
+        //Funnels hold vertex indices, with None for the source. Each triangle deeper widens the
+        //funnel by at most one slot on one side, so starting in the middle leaves room for both
+        let mut buffer: Vec<Option<usize>> = vec![None; 2 * n + 3];
+        let mid = n + 1;
+        let point = |v: Option<usize>| v.map_or(s, |i| &pl[i]);
+        let mut undo: Vec<(usize, Option<usize>)> = Vec::new();
+        let mut parents = vec![None; n];
+        let mut distances = vec![f64::INFINITY; n];
+
+        let root = rooted.order[0];
+        for v in rooted.dcel.get_face_indices(rooted.dual.get_face(root)) {
+            distances[v] = polyline_length(&[s.clone(), pl[v].clone()]);
+        }
+        let mut stack = vec![FunnelFrame {
+            node: root,
+            lo: mid,
+            apex: mid,
+            hi: mid,
+            opposite: usize::MAX,
+            tangent: mid,
+            undo: 0,
+        }];
+        for &node in &rooted.order[1..] {
+            let (from, (left, right)) = rooted.entry(node).unwrap();
+            while stack.last().unwrap().node != from {
+                let frame = stack.pop().unwrap();
+                for (slot, v) in undo.drain(frame.undo..).rev() {
+                    buffer[slot] = v;
+                }
+            }
+            let top = stack.last().unwrap();
+            let mark = undo.len();
+            let mut write = |slot: usize, v: usize| {
+                undo.push((slot, buffer[slot]));
+                buffer[slot] = Some(v);
+            };
+            let (lo, apex, hi) = if from == root {
+                write(mid - 1, left);
+                write(mid + 1, right);
+                (mid - 1, mid, mid + 1)
+            } else if left == top.opposite {
+                write(top.tangent - 1, left);
+                (top.tangent - 1, top.apex.max(top.tangent), top.hi)
+            } else {
+                write(top.tangent + 1, right);
+                (top.lo, top.apex.min(top.tangent), top.tangent + 1)
+            };
+            let opposite = rooted
+                .dcel
+                .get_face_indices(rooted.dual.get_face(node))
+                .into_iter()
+                .find(|&v| v != left && v != right)
+                .unwrap();
+            //Going out from the apex, the opposite vertex lies beyond each funnel edge up to the
+            //tangent and short of every edge after it. In line with an edge counts as short of
+            //it, so a path running straight through a vertex does not bend there
+            let q = &pl[opposite];
+            let tangent = first_failing(lo, hi, |j| {
+                let turn = Point::cross(point(buffer[j]), point(buffer[j + 1]), q);
+                if j < apex {
+                    turn >= 0
+                } else {
+                    turn < 0
+                }
+            });
+            let parent = buffer[tangent];
+            let base = parent.map_or(0.0, |u| distances[u]);
+            parents[opposite] = parent;
+            distances[opposite] = base + polyline_length(&[point(parent).clone(), q.clone()]);
+            stack.push(FunnelFrame {
+                node,
+                lo,
+                apex,
+                hi,
+                opposite,
+                tangent,
+                undo: mark,
+            });
+        }
+        Some(ShortestPathTree {
+            source: s.clone(),
+            point_list: pl.clone(),
+            rooted,
+            parents,
+            distances,
+        })
+    }
+
+    pub fn get_source(&self) -> &Point {
+        &self.source
+    }
+
+    pub fn get_parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    pub fn get_distance(&self, v: usize) -> f64 {
+        self.distances[v]
+    }
+
+    //Path from the source to vertex v, read off the tree
+    pub fn get_path(&self, v: usize) -> Vec<Point> {
+        let mut r = vec![self.point_list[v].clone()];
+        let mut v = v;
+        while let Some(u) = self.parents[v] {
+            r.push(self.point_list[u].clone());
+            v = u;
+        }
+        r.push(self.source.clone());
+        r.reverse();
+        r
+    }
+
+    //Shortest path from the source to any point strictly inside the polygon
+    pub fn geodesic_path(&self, q: &Point) -> Option<ShortestPath> {
+        if !strictly_inside(&self.point_list, q) {
+            return None;
+        }
+        let points = pull_taut(&self.source, self.rooted.sleeve(self.rooted.locate(q)), q);
+        let length = polyline_length(&points);
+        Some(ShortestPath { points, length })
+    }
+
+    pub fn geodesic_distance(&self, q: &Point) -> Option<f64> {
+        self.geodesic_path(q).map(|p| p.length)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MinLinkError {
+    //s and t have to be strictly inside the polygon
+    NotInside,
+    //A bend needs more than i128 to be written down exactly. Each window can lean on a bend of
+    //the one before, so the bits needed grow along the path. See min_link_path for the bound
+    Overflow,
+}
+
+//Path with the fewest segments. Its bends are generally not lattice points, so they are kept as
+//exact rationals
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinLinkPath {
+    pub points: Vec<RationalPoint>,
+    pub links: usize,
+}
+
+impl From<Overflow> for MinLinkError {
+    fn from(_: Overflow) -> Self {
+        MinLinkError::Overflow
+    }
+}
+
+//Lines crossing every portal with the left end on their non-negative side and the right end on
+//their non-positive side. Any such line can be turned about the portal ends until it passes
+//through two of them, which leaves it on a corner of each hull or along a hull side, so those are
+//the only lines tried. That includes every line that is extreme in some direction
+fn stabbing_lines(portals: &[(Vertex, Vertex)]) -> Result<Vec<Line>, MinLinkError> {
+    let left = hull(portals.iter().map(|p| p.0).collect())?;
+    let right = hull(portals.iter().map(|p| p.1).collect())?;
+    let around = |h: &[Vertex], i: usize| {
+        let n = h.len();
+        [h[(i + n - 1) % n], h[(i + 1) % n]]
+    };
+    let separates = |line: &Line| -> Result<bool, MinLinkError> {
+        for p in &left {
+            if line.side(p)? < 0 {
+                return Ok(false);
+            }
+        }
+        for p in &right {
+            if line.side(p)? > 0 {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    };
+
+    let mut lines = Vec::new();
+    for (i, a) in left.iter().enumerate() {
+        for (j, b) in right.iter().enumerate() {
+            if a == b {
+                continue;
+            }
+            //Both hulls lie on one side of the line exactly when the corners next to a and b do
+            let line = Line::through(a, b)?;
+            let mut sides = Vec::new();
+            for p in around(&left, i) {
+                sides.push(line.side(&p)?);
+            }
+            for p in around(&right, j) {
+                sides.push(-line.side(&p)?);
+            }
+            if sides.iter().all(|&s| s >= 0) {
+                lines.push(line);
+            }
+            if sides.iter().all(|&s| s <= 0) {
+                lines.push(line.reversed());
+            }
+        }
+    }
+    for h in [&left, &right] {
+        for i in 0..h.len() {
+            let (a, b) = (h[i], h[(i + 1) % h.len()]);
+            if a == b {
+                continue;
+            }
+            let line = Line::through(&a, &b)?;
+            for line in [line, line.reversed()] {
+                if separates(&line)? {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    Ok(lines)
+}
+
+//Path from s to t with the fewest links, both strictly inside the polygon. Following Suri, the
+//sleeve of triangles between them is walked greedily: each window is the chord beyond which the
+//region seen from the previous window ends, so every point past it needs one more link. The
+//path is then read back by meeting each window's line with the window before it. Everything is
+//exact, but the bends can need more bits than the input, in which case this gives up with
+//MinLinkError::Overflow.
+//With every coordinate at most 2^m in absolute value, a window line passes through at most one
+//bend of the window before and lattice points, so the denominators of the bends grow by at most
+//8 * 4^m per window, and the largest product formed is below 8 * 8^m * (8 * 4^m)^(2L - 2) for a
+//path of L links. Paths with 3m + 3 + (2L - 2)(2m + 3) <= 126 therefore never overflow: up to 6
+//links with coordinates within 16, and up to 3 within 1024
+pub fn min_link_path(
+    polygon: &SimplePolygon,
+    s: &Point,
+    t: &Point,
+) -> Result<MinLinkPath, MinLinkError> {
+    let pl = polygon.get_point_list();
+    if !strictly_inside(pl, s) || !strictly_inside(pl, t) {
+        return Err(MinLinkError::NotInside);
+    }
+    if s == t {
+        return Ok(MinLinkPath {
+            points: vec![RationalPoint::from_point(s)],
+            links: 0,
+        });
+    }
+    let rt = RootedTriangulation::new(polygon, s);
+    let portals = sleeve_portals(s, rt.sleeve(rt.locate(t)), t);
+    let exact = |(l, r): &(Point, Point)| (Vertex::from_point(l), Vertex::from_point(r));
+
+    let last = portals.len() - 1;
+    let through = |source: (Vertex, Vertex), upto: usize, from: usize| {
+        let mut ps = vec![source];
+        ps.extend(portals[from..=upto].iter().map(exact));
+        stabbing_lines(&ps)
+    };
+    //Line of each window, with one of its ends
+    let mut windows: Vec<(Line, Vertex)> = Vec::new();
+    let (mut source, mut next) = (exact(&portals[0]), 1);
+    let finish = loop {
+        if let Some(&line) = through(source, last, next)?.first() {
+            break line;
+        }
+        //Portals reachable in one more link form a prefix, so binary search for its end
+        let (mut lo, mut hi) = (next, last);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if through(source, mid, next)?.is_empty() {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        let lines = through(source, lo, next)?;
+        let (l, r) = &portals[lo];
+        //The vertex the next portal adds, or t itself, lies on the same side of every line
+        let q = if &portals[lo + 1].0 == l {
+            &portals[lo + 1].1
+        } else {
+            &portals[lo + 1].0
+        };
+        let vq = Vertex::from_point(q);
+        let q_left = lines[0].side(&vq)? > 0;
+        //The window meets the segment from the portal end on the other side to q as far along
+        //as it can
+        let pivot = if q_left { r } else { l };
+        let towards = Line::through(&Vertex::from_point(pivot), &vq)?;
+        //How far along from the pivot towards q, scaled by the w of the point
+        let (dx, dy) = ((q.x - pivot.x) as i128, (q.y - pivot.y) as i128);
+        let base = pivot.x as i128 * dx + pivot.y as i128 * dy;
+        let along = |v: &Vertex| -> Result<(i128, i128), MinLinkError> {
+            let dot = det(v.x, -v.y, dy, dx)?
+                .checked_sub(mul(base, v.w)?)
+                .ok_or(MinLinkError::Overflow)?;
+            Ok((dot, v.w))
+        };
+        let mut best: Option<(Line, Vertex, (i128, i128))> = None;
+        for line in lines {
+            let Some(c) = line.meet(&towards)? else {
+                continue;
+            };
+            let key = along(&c)?;
+            let further = match &best {
+                Some((_, _, b)) => mul(key.0, b.1)? > mul(b.0, key.1)?,
+                None => true,
+            };
+            if further {
+                best = Some((line, c, key));
+            }
+        }
+        //Every line crosses from the pivot's side to q's, so it meets the segment between them
+        let (w, c, _) = best.unwrap();
+        let (vl, vr) = exact(&portals[lo]);
+        //A window lying along the portal leaves its near end there
+        let a = w
+            .meet(&Line::through(&vl, &vr)?)?
+            .unwrap_or(if q_left { vr } else { vl });
+        source = if q_left { (a, c) } else { (c, a) };
+        windows.push((w, a));
+        next = lo + 1;
+    };
+
+    let mut points = vec![RationalPoint::from_point(s)];
+    for (k, (w, end)) in windows.iter().enumerate() {
+        let line = windows.get(k + 1).map_or(&finish, |next| &next.0);
+        //A line through the window that does not cross it runs along it
+        points.push(line.meet(w)?.unwrap_or(*end).to_rational());
+    }
+    points.push(RationalPoint::from_point(t));
+    let links = points.len() - 1;
+    Ok(MinLinkPath { points, links })
+}
+
+#[cfg(test)]
+mod path_tests {
+    use super::*;
+    use crate::fixtures::{notched_square, points};
+    use crate::primitives::Rational;
+    #[test]
+    fn test_shortest_path() {
+        let p = notched_square();
+        let r = shortest_path(&p, &Point::new(2, 8), &Point::new(8, 8)).unwrap();
+        assert_eq!(r.points, points(&[(2, 8), (4, 4), (6, 4), (8, 8)]));
+        assert!((r.length - (2.0 * 20f64.sqrt() + 2.0)).abs() < 1e-9);
+
+        let r = shortest_path(&p, &Point::new(8, 8), &Point::new(1, 1)).unwrap();
+        assert_eq!(r.points, points(&[(8, 8), (6, 4), (1, 1)]));
+
+        let r = shortest_path(&p, &Point::new(1, 1), &Point::new(9, 2)).unwrap();
+        assert_eq!(r.points, points(&[(1, 1), (9, 2)]));
+
+        assert!(shortest_path(&p, &Point::new(5, 8), &Point::new(1, 1)).is_none());
+    }
+    #[test]
+    fn test_shortest_path_tree() {
+        let p = notched_square();
+        let t = ShortestPathTree::new(&p, &Point::new(2, 8)).unwrap();
+        assert_eq!(t.get_parent(0), None);
+        assert_eq!(t.get_parent(4), Some(5));
+        assert_eq!(t.get_parent(2), Some(4));
+        assert_eq!(t.get_parent(3), Some(4));
+        assert_eq!(t.get_parent(1), Some(5));
+        assert!((t.get_distance(4) - (20f64.sqrt() + 2.0)).abs() < 1e-9);
+        assert_eq!(
+            t.get_path(2),
+            [(2, 8), (4, 4), (6, 4), (10, 10)]
+                .iter()
+                .map(|&(x, y)| Point::new(x, y))
+                .collect::<Vec<Point>>()
+        );
+
+        let d = t.geodesic_distance(&Point::new(8, 8)).unwrap();
+        assert!((d - (2.0 * 20f64.sqrt() + 2.0)).abs() < 1e-9);
+        assert_eq!(t.geodesic_distance(&Point::new(1, 8)), Some(1.0));
+        assert_eq!(t.geodesic_distance(&Point::new(5, 8)), None);
+    }
+    #[test]
+    fn test_shortest_path_tree_straight() {
+        //Two nested hooks around a notch opening towards the source
+        let p = SimplePolygon::from_point_list(points(&[
+            (9, 10),
+            (9, 6),
+            (16, 6),
+            (16, 16),
+            (5, 16),
+            (5, 0),
+            (22, 0),
+            (22, 21),
+            (0, 21),
+            (0, 19),
+            (20, 19),
+            (20, 2),
+            (7, 2),
+            (7, 14),
+            (14, 14),
+            (14, 8),
+            (11, 8),
+            (11, 10),
+        ]));
+        let t = ShortestPathTree::new(&p, &Point::new(11, 7)).unwrap();
+        //Straight up through (11, 8), which is passed rather than bent around
+        assert_eq!(t.get_parent(17), None);
+        assert_eq!(t.get_distance(17), 3.0);
+        assert_eq!(t.get_parent(3), Some(15));
+        assert_eq!(t.get_parent(9), Some(10));
+        for v in 0..18 {
+            let path = t.get_path(v);
+            assert!((polyline_length(&path) - t.get_distance(v)).abs() < 1e-9);
+            assert!(path
+                .windows(3)
+                .all(|w| Point::cross(&w[0], &w[1], &w[2]) != 0));
+        }
+    }
+    #[test]
+    fn test_min_link_path() {
+        //A corridor with one notch down from the top and one up from the bottom
+        let p = SimplePolygon::from_point_list(
+            [
+                (0, 0),
+                (12, 0),
+                (12, 8),
+                (14, 8),
+                (14, 0),
+                (20, 0),
+                (20, 10),
+                (8, 10),
+                (8, 2),
+                (6, 2),
+                (6, 10),
+                (0, 10),
+            ]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect(),
+        );
+        let link = |s: (isize, isize), t: (isize, isize)| {
+            min_link_path(&p, &Point::new(s.0, s.1), &Point::new(t.0, t.1)).unwrap()
+        };
+        let exact = |v: &[(isize, isize)]| -> Vec<RationalPoint> {
+            v.iter()
+                .map(|&(x, y)| RationalPoint::from_point(&Point::new(x, y)))
+                .collect()
+        };
+        assert_eq!(link((1, 1), (10, 1)).points, exact(&[(1, 1), (10, 1)]));
+        assert_eq!(link((2, 8), (10, 8)).links, 2);
+        assert_eq!(link((10, 5), (3, 9)).links, 2);
+
+        let r = link((2, 8), (18, 2));
+        assert_eq!(r.links, 3);
+        //No link cuts through either notch
+        let in_notch = |(x, y): (f64, f64)| {
+            let e = 1e-6;
+            (x > 6.0 + e && x < 8.0 - e && y > 2.0 + e)
+                || (x > 12.0 + e && x < 14.0 - e && y < 8.0 - e)
+        };
+        let points: Vec<(f64, f64)> = r.points.iter().map(|p| p.to_f64()).collect();
+        for w in points.windows(2) {
+            for k in 0..=100 {
+                let u = k as f64 / 100.0;
+                let q = (
+                    w[0].0 + u * (w[1].0 - w[0].0),
+                    w[0].1 + u * (w[1].1 - w[0].1),
+                );
+                assert!(!in_notch(q));
+            }
+        }
+
+        assert_eq!(
+            min_link_path(&p, &Point::new(7, 5), &Point::new(1, 1)),
+            Err(MinLinkError::NotInside)
+        );
+    }
+    #[test]
+    fn test_min_link_spiral() {
+        //A corridor winding inwards from the bottom left through four turns
+        let p = SimplePolygon::from_point_list(points(&[
+            (0, 0),
+            (10, 0),
+            (10, 10),
+            (2, 10),
+            (2, 4),
+            (6, 4),
+            (6, 6),
+            (4, 6),
+            (4, 8),
+            (8, 8),
+            (8, 2),
+            (0, 2),
+        ]));
+        let link = |s: (isize, isize), t: (isize, isize)| {
+            min_link_path(&p, &Point::new(s.0, s.1), &Point::new(t.0, t.1)).unwrap()
+        };
+        let r = link((5, 5), (1, 1));
+        let expected: Vec<RationalPoint> = [(10, 10), (4, 16), (20, 16), (15, 1), (2, 2)]
+            .iter()
+            .map(|&(x, y)| RationalPoint::new(Rational::new(x, 2), Rational::new(y, 2)))
+            .collect();
+        assert_eq!(r.points, expected);
+        assert_eq!(link((1, 1), (5, 5)).links, 4);
+        assert_eq!(link((1, 1), (3, 9)).links, 3);
+
+        let r = link((3, 7), (3, 7));
+        assert_eq!(r.links, 0);
+        assert_eq!(r.points, vec![RationalPoint::from_point(&Point::new(3, 7))]);
+        assert_eq!(link((1, 1), (9, 9)).links, 2);
+        //Seen directly, up the right arm
+        let r = link((9, 1), (9, 9));
+        assert_eq!(r.links, 1);
+        assert_eq!(r.points.len(), 2);
+    }
+    #[test]
+    fn test_min_link_bound() {
+        //A spiral within 16 whose path needs 6 links, as many as the bound allows there. Its
+        //bends are not lattice points, and their denominators grow along the path
+        let p = SimplePolygon::from_point_list(points(&[
+            (9, 5),
+            (9, 11),
+            (3, 11),
+            (3, 0),
+            (12, 0),
+            (12, 14),
+            (0, 14),
+            (0, 12),
+            (10, 12),
+            (10, 2),
+            (5, 2),
+            (5, 9),
+            (7, 9),
+            (7, 5),
+        ]));
+        let r = min_link_path(&p, &Point::new(1, 13), &Point::new(8, 6)).unwrap();
+        assert_eq!(r.links, 6);
+        let expected: Vec<RationalPoint> = [
+            ((1, 1), (13, 1)),
+            ((12, 1), (106, 9)),
+            ((211, 22), (0, 1)),
+            ((3, 1), (290, 101)),
+            ((3499, 619), (11, 1)),
+            ((7, 1), (9, 1)),
+            ((8, 1), (6, 1)),
+        ]
+        .iter()
+        .map(|&((a, b), (c, d))| RationalPoint::new(Rational::new(a, b), Rational::new(c, d)))
+        .collect();
+        assert_eq!(r.points, expected);
+        assert_eq!(
+            min_link_path(&p, &Point::new(8, 6), &Point::new(1, 13)).map(|r| r.links),
+            Ok(6)
+        );
+    }
+}
@@ -1,10 +1,12 @@
 // GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
 // Date: April 14, 2022
 // polygon.rs - Code for generating and partitioning polygons
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::{HashMap, HashSet};
 
-use crate::primitives::{DirEdge, Point, PointOrientation};
+use crate::dcel::DCEL;
+use crate::primitives::{DirEdge, Point, PointOrientation, RationalPoint};
 
 #[derive(Debug)]
 struct UnorderedEdge<'a> {
@@ -44,6 +46,41 @@ pub struct SimplePolygon {
     point_list: Vec<Point>, //Circular list of points
 }
 
+//Polygon with exact but not necessarily integer vertices, as produced by clipping a
+//SimplePolygon. Counterclockwise like SimplePolygon
+#[derive(Debug, Clone, PartialEq)]
+pub struct RationalPolygon {
+    point_list: Vec<RationalPoint>,
+}
+
+impl RationalPolygon {
+    pub fn from_point_list(pl: Vec<RationalPoint>) -> Self {
+        RationalPolygon { point_list: pl }
+    }
+    pub fn get_point_list(&self) -> &Vec<RationalPoint> {
+        &self.point_list
+    }
+    //Rounds every vertex to the lattice after multiplying by scale. Fails when the rounded
+    //boundary is not simple, as when rounding merges non-consecutive vertices, leaves fewer than
+    //three, or brings a vertex across a nearby edge
+    pub fn to_simple_polygon(&self, scale: isize) -> Option<SimplePolygon> {
+        let mut pl: Vec<Point> = Vec::new();
+        for p in &self.point_list {
+            let p = p.round(scale);
+            if pl.last() != Some(&p) {
+                pl.push(p);
+            }
+        }
+        while pl.len() > 1 && pl.first() == pl.last() {
+            pl.pop();
+        }
+        if !is_simple(&pl) {
+            return None;
+        }
+        Some(SimplePolygon { point_list: pl })
+    }
+}
+
 #[derive(Debug, PartialEq)]
 enum PointType {
     Split,
@@ -53,6 +90,19 @@ enum PointType {
     Regular,
 }
 
+//How many vertices of each kind a counterclockwise polygon has. Split and merge vertices are the
+//ones the monotone partition has to add diagonals for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PolygonStats {
+    pub vertex_count: usize,
+    pub reflex_count: usize,
+    pub start_count: usize,
+    pub end_count: usize,
+    pub split_count: usize,
+    pub merge_count: usize,
+    pub regular_count: usize,
+}
+
 impl SimplePolygon {
     pub fn get_point_list(&self) -> &Vec<Point> {
         &self.point_list
@@ -108,34 +158,54 @@ impl SimplePolygon {
         }
     }
 
+    pub fn get_stats(&self) -> PolygonStats {
+        let mut r = PolygonStats {
+            vertex_count: self.point_list.len(),
+            ..Default::default()
+        };
+        for i in 0..self.point_list.len() {
+            if self.is_reflex(i) {
+                r.reflex_count += 1;
+            }
+            match self.get_point_type(i) {
+                PointType::Start => r.start_count += 1,
+                PointType::End => r.end_count += 1,
+                PointType::Split => r.split_count += 1,
+                PointType::Merge => r.merge_count += 1,
+                PointType::Regular => r.regular_count += 1,
+            }
+        }
+        r
+    }
+
     pub fn partition_monotone(&self) -> Vec<DirEdge> {
         let mut ret = Vec::new();
         let traps = self.partition_trapezoid();
         for t in traps.0 {
-            if self.get_point_type(t.top_vertex.0) == PointType::Merge {
-                ret.push(DirEdge::from_points(t.top_vertex.1, t.bottom_vertex.1));
-            } else if self.get_point_type(t.bottom_vertex.0) == PointType::Split {
+            if self.get_point_type(t.top_vertex.0) == PointType::Merge
+                || self.get_point_type(t.bottom_vertex.0) == PointType::Split
+            {
                 ret.push(DirEdge::from_points(t.top_vertex.1, t.bottom_vertex.1));
             }
         }
         ret
     }
 
-    pub fn partition_trapezoid(&self) -> Trapezoidalization {
+    pub fn partition_trapezoid(&self) -> Trapezoidalization<'_> {
         let point_list = &self.point_list;
         let get_edgepoints = |point_index| {
             let p1 = &point_list[point_index];
             let p2 = &point_list[self.get_next_index(point_index)];
             if p1.is_higher_than(p2) {
-                return UnorderedEdge {
+                UnorderedEdge {
                     higher: p1,
                     lower: p2,
-                };
+                }
             } else {
-                return UnorderedEdge {
+                UnorderedEdge {
                     higher: p2,
                     lower: p1,
-                };
+                }
             }
         };
 
@@ -159,7 +229,7 @@ impl SimplePolygon {
             if point_list[*a].is_higher_than(&point_list[*b]) {
                 return std::cmp::Ordering::Greater;
             }
-            return std::cmp::Ordering::Less;
+            std::cmp::Ordering::Less
         });
         event_queue.reverse();
 
@@ -204,8 +274,7 @@ impl SimplePolygon {
                     } else {
                         let mut r = sweep_line_status.len();
                         #[cfg(debug_assertions)]
-                        for sl_idx in 0..sweep_line_status.len() {
-                            let (left_idx, right_idx) = sweep_line_status[sl_idx];
+                        for &(left_idx, right_idx) in &sweep_line_status {
                             let left_ep = get_edgepoints(left_idx);
                             let right_ep = get_edgepoints(right_idx);
                             if !on_left(&left_ep, curr) {
@@ -214,8 +283,9 @@ impl SimplePolygon {
                                 assert!(on_left(&right_ep, curr));
                             }
                         }
-                        for sl_idx in 0..sweep_line_status.len() {
-                            let (left_idx, _right_idx) = sweep_line_status[sl_idx];
+                        for (sl_idx, &(left_idx, _right_idx)) in
+                            sweep_line_status.iter().enumerate()
+                        {
                             let left_ep = get_edgepoints(left_idx);
                             if on_left(&left_ep, curr) {
                                 r = sl_idx;
@@ -233,20 +303,18 @@ impl SimplePolygon {
                         panic!("This is not possible");
                     } else {
                         let mut r = 0;
-                        for sl_idx in 0..sweep_line_status.len() {
-                            let (left_idx, right_idx) = sweep_line_status[sl_idx];
+                        for (sl_idx, &(left_idx, right_idx)) in sweep_line_status.iter().enumerate()
+                        {
                             let left_ep = get_edgepoints(left_idx);
                             let right_ep = get_edgepoints(right_idx);
-                            if !on_left(&left_ep, curr) {
-                                if on_left(&right_ep, curr) {
-                                    r = sl_idx;
-                                    break;
-                                }
+                            if !on_left(&left_ep, curr) && on_left(&right_ep, curr) {
+                                r = sl_idx;
+                                break;
                             }
                         }
                         #[cfg(debug_assertions)]
-                        for sl_idx in 0..sweep_line_status.len() {
-                            let (left_idx, right_idx) = sweep_line_status[sl_idx];
+                        for (sl_idx, &(left_idx, right_idx)) in sweep_line_status.iter().enumerate()
+                        {
                             let left_ep = get_edgepoints(left_idx);
                             let right_ep = get_edgepoints(right_idx);
                             if sl_idx < r {
@@ -365,17 +433,44 @@ impl SimplePolygon {
         if !pl.iter().all(|x| uniq.insert(x)) {
             panic!("Non unique elements");
         };
-        return SimplePolygon { point_list: pl };
+        SimplePolygon { point_list: pl }
+    }
+
+    //Like from_point_list, reversing the points first if they run clockwise
+    pub fn from_point_list_ccw(mut pl: Vec<Point>) -> SimplePolygon {
+        if is_clockwise(&pl) {
+            pl.reverse();
+        }
+        Self::from_point_list(pl)
     }
 
     pub fn gen_rand_hard(vertex_count: usize, max_coord: usize, retry_cnt: usize) -> Option<Self> {
+        Self::gen_rand_hard_with_rng(vertex_count, max_coord, retry_cnt, &mut rand::thread_rng())
+    }
+
+    //Same polygon every time for the same seed, so that one that broke something can be rebuilt
+    pub fn gen_rand_hard_seeded(
+        vertex_count: usize,
+        max_coord: usize,
+        retry_cnt: usize,
+        seed: u64,
+    ) -> Option<Self> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self::gen_rand_hard_with_rng(vertex_count, max_coord, retry_cnt, &mut rng)
+    }
+
+    pub fn gen_rand_hard_with_rng<R: Rng + ?Sized>(
+        vertex_count: usize,
+        max_coord: usize,
+        retry_cnt: usize,
+        rng: &mut R,
+    ) -> Option<Self> {
         if vertex_count < 3 {
             return None;
         }
-        let engine = || {
+        let mut engine = || {
             let mut point_list: Vec<Point> = Vec::new();
 
-            let mut rng = rand::thread_rng();
             let mut gen_rand_point = || {
                 let rand_x = rng.gen_range(0..max_coord);
                 let rand_y = rng.gen_range(0..max_coord);
@@ -431,7 +526,7 @@ impl SimplePolygon {
             //This can be done much much better
             for i in 0..vertex_count - 2 {
                 use crate::primitives::*;
-                let p0 = &point_list[i + 0];
+                let p0 = &point_list[i];
                 let p1 = &point_list[i + 1];
                 let p2 = &point_list[i + 2];
                 if let PointOrientation::Collinear = Point::orientation(p0, p1, p2) {
@@ -452,6 +547,23 @@ impl SimplePolygon {
         }
         None
     }
+    //Triangulates by splitting into monotone pieces first. Points of the returned DCEL have the
+    //same indices as in the point list, which may run either way round
+    pub fn triangulate(&self) -> DCEL {
+        let mut dcel = DCEL::from_simple_polygon(self);
+        //The sweep classifies vertices by the turn they make, which needs counterclockwise order
+        let ccw = SimplePolygon::from_point_list_ccw(self.point_list.clone());
+        dcel.add_trusted_diagonals(&ccw.partition_monotone());
+
+        let mut diagonals = Vec::new();
+        for face in dcel.get_internal_faces() {
+            let p = SimplePolygon::from_point_list(dcel.get_point_list(face));
+            diagonals.append(&mut p.triangulate_monotone());
+        }
+        dcel.add_trusted_diagonals(&diagonals);
+        dcel
+    }
+
     pub fn triangulate_monotone(&self) -> Vec<DirEdge> {
         #[cfg(debug_assertions)]
         {
@@ -464,7 +576,7 @@ impl SimplePolygon {
             if point_list[*a].is_higher_than(&point_list[*b]) {
                 return std::cmp::Ordering::Greater;
             }
-            return std::cmp::Ordering::Less;
+            std::cmp::Ordering::Less
         });
         event_queue.reverse();
 
@@ -474,18 +586,9 @@ impl SimplePolygon {
         stack.push(event_queue[0]);
         stack.push(event_queue[1]);
 
-        let is_adjacent = |i1, i2| {
-            if self.get_next_index(i1) == i2 {
-                true
-            } else if self.get_next_index(i2) == i1 {
-                true
-            } else {
-                false
-            }
-        };
+        let is_adjacent = |i1, i2| self.get_next_index(i1) == i2 || self.get_next_index(i2) == i1;
 
-        for idx in 2..event_queue.len() {
-            let i = event_queue[idx];
+        for &i in event_queue.iter().skip(2) {
             debug_assert!(stack.len() > 1);
             if is_adjacent(i, stack[0]) {
                 while stack.len() > 1 {
@@ -529,6 +632,101 @@ impl SimplePolygon {
             .collect()
     }
 }
+//Whether the closed boundary through the points runs clockwise, by the sign of its area
+pub(crate) fn is_clockwise(pl: &[Point]) -> bool {
+    let n = pl.len();
+    let a2: i128 = (0..n)
+        .map(|i| Point::cross(&pl[0], &pl[i], &pl[(i + 1) % n]))
+        .sum();
+    a2 < 0
+}
+
+//Shamos and Hoey's sweep in O(n log n): distinct vertices, and no two edges meet other than
+//neighbours at their shared end. Shearing x to x * K + y with K above twice any |y| gives every
+//vertex its own x, so no edge is vertical and the edges starting or ending at a vertex are
+//exactly its two sides. Exact for coordinates below 2^40 in absolute value
+pub(crate) fn is_simple(pl: &[Point]) -> bool {
+    let n = pl.len();
+    if n < 3 || pl.iter().collect::<HashSet<_>>().len() != n {
+        return false;
+    }
+    let k = 2 * pl.iter().map(|p| p.y.unsigned_abs()).max().unwrap() as i128 + 1;
+    let key = |p: &Point| (p.x as i128 * k + p.y as i128, p.y as i128);
+    let edges: Vec<DirEdge> = (0..n)
+        .map(|i| DirEdge::from_points(&pl[i], &pl[(i + 1) % n]))
+        .collect();
+    let ends = |e: usize| {
+        let (a, b) = (key(&edges[e].start), key(&edges[e].end));
+        if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    };
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&i| key(&pl[i]));
+    let mut status: Vec<usize> = Vec::new();
+    for i in order {
+        let (x, y) = key(&pl[i]);
+        let sides = [(i + n - 1) % n, i];
+        for &e in &sides {
+            if ends(e).1 == (x, y) {
+                let j = status.iter().position(|&f| f == e).unwrap();
+                status.remove(j);
+                if j > 0 && j < status.len() && edges[status[j - 1]].crosses(&edges[status[j]]) {
+                    return false;
+                }
+            }
+        }
+        for &e in &sides {
+            if ends(e).0 != (x, y) {
+                continue;
+            }
+            //Edges by height at x, and those through this vertex by slope
+            let ((ax, ay), (bx, by)) = ends(e);
+            let j = status.partition_point(|&f| {
+                let ((fx, fy), (gx, gy)) = ends(f);
+                let (dx, dy) = (gx - fx, gy - fy);
+                match (fy * dx + dy * (x - fx)).cmp(&(y * dx)) {
+                    std::cmp::Ordering::Equal => dy * (bx - ax) < (by - ay) * dx,
+                    o => o == std::cmp::Ordering::Less,
+                }
+            });
+            status.insert(j, e);
+            for f in [j.checked_sub(1), Some(j + 1)].into_iter().flatten() {
+                if status.get(f).is_some_and(|&f| edges[e].crosses(&edges[f])) {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+//Applies 2-opt moves to the closed tour until no two of its edges cross. Each move shortens the
+//tour, so this ends. The points must be in general position
+pub(crate) fn untangle(pl: &mut [Point]) {
+    let n = pl.len();
+    let edge = |pl: &[Point], i: usize| DirEdge::from_points(&pl[i], &pl[(i + 1) % n]);
+    loop {
+        let mut untangled = true;
+        for i in 0..n {
+            for j in i + 2..n {
+                if (j + 1) % n == i {
+                    continue;
+                }
+                if edge(pl, i).intersects(&edge(pl, j)) {
+                    pl[i + 1..=j].reverse();
+                    untangled = false;
+                }
+            }
+        }
+        if untangled {
+            break;
+        }
+    }
+}
+
 #[cfg(test)]
 mod polygon_tests {
     use super::*;
@@ -537,4 +735,12 @@ mod polygon_tests {
         let p = SimplePolygon::gen_rand_hard(3, 5, 100).unwrap();
         println!("{:?} -- {}", p, p.get_determinant());
     }
+    #[test]
+    fn test_seeded_generation() {
+        let a = SimplePolygon::gen_rand_hard_seeded(8, 100, 100, 2026).unwrap();
+        let b = SimplePolygon::gen_rand_hard_seeded(8, 100, 100, 2026).unwrap();
+        assert_eq!(a.get_point_list(), b.get_point_list());
+        let c = SimplePolygon::gen_rand_hard_seeded(8, 100, 100, 2027).unwrap();
+        assert_ne!(a.get_point_list(), c.get_point_list());
+    }
 }
@@ -0,0 +1,191 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// polygonize.rs - Simple polygons through a given set of points
+use crate::polygon::{untangle, SimplePolygon};
+use crate::primitives::Point;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonizeMode {
+    //Sorted by angle around a point inside the hull
+    Star,
+    //Lower chain left to right, then upper chain back
+    XMonotone,
+    //Random order untangled by 2-opt moves
+    TwoOpt,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PolygonizeError {
+    TooFewPoints(usize),
+    DuplicatePoint(Point),
+    //Three points in line that the mode would have to join one after another, or for TwoOpt,
+    //any three points in line
+    CollinearPoints(Point, Point, Point),
+}
+
+//Simple counterclockwise polygon with exactly the given points as vertices. Star and XMonotone
+//run in O(n log n) and only fail when their ordering puts three points in line, while TwoOpt
+//needs the points in general position and takes O(n^2) per pass, which suits a few hundred points
+pub fn polygonize<R: Rng + ?Sized>(
+    points: Vec<Point>,
+    mode: PolygonizeMode,
+    rng: &mut R,
+) -> Result<SimplePolygon, PolygonizeError> {
+    if points.len() < 3 {
+        return Err(PolygonizeError::TooFewPoints(points.len()));
+    }
+    let mut seen = HashSet::with_capacity(points.len());
+    if let Some(p) = points.iter().find(|&p| !seen.insert(p)) {
+        return Err(PolygonizeError::DuplicatePoint(p.clone()));
+    }
+    let pl = match mode {
+        PolygonizeMode::Star => star_order(points)?,
+        PolygonizeMode::XMonotone => monotone_order(points),
+        PolygonizeMode::TwoOpt => {
+            if let Some((a, b, c)) = find_collinear(&points) {
+                return Err(PolygonizeError::CollinearPoints(a, b, c));
+            }
+            let mut pl = points;
+            pl.shuffle(rng);
+            untangle(&mut pl);
+            pl
+        }
+    };
+    let n = pl.len();
+    for i in 0..n {
+        let (p, q, r) = (&pl[i], &pl[(i + 1) % n], &pl[(i + 2) % n]);
+        if Point::cross(p, q, r) == 0 {
+            return Err(PolygonizeError::CollinearPoints(
+                p.clone(),
+                q.clone(),
+                r.clone(),
+            ));
+        }
+    }
+    Ok(SimplePolygon::from_point_list_ccw(pl))
+}
+
+//The centre is the centroid of three points not in line, so it lies strictly inside the hull
+//and every angular gap is less than a half turn. Work is done at three times the scale to keep
+//it on the lattice. Points on one ray from the centre are taken outwards, which keeps the edge
+//between them on the ray and apart from the rest
+fn star_order(pl: Vec<Point>) -> Result<Vec<Point>, PolygonizeError> {
+    let (a, b) = (&pl[0], &pl[1]);
+    let c = pl[2..]
+        .iter()
+        .find(|c| Point::cross(a, b, c) != 0)
+        .ok_or_else(|| PolygonizeError::CollinearPoints(a.clone(), b.clone(), pl[2].clone()))?;
+    let centre = Point::new(a.x + b.x + c.x, a.y + b.y + c.y);
+    let mut scaled: Vec<(Point, usize)> = pl
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (Point::new(3 * p.x, 3 * p.y), i))
+        .collect();
+    let dist = |p: &Point| {
+        let (dx, dy) = ((p.x - centre.x) as i128, (p.y - centre.y) as i128);
+        dx * dx + dy * dy
+    };
+    scaled.sort_by(|(p, _), (q, _)| {
+        Point::angular_cmp(&centre, p, q).then_with(|| dist(p).cmp(&dist(q)))
+    });
+    Ok(scaled.into_iter().map(|(_, i)| pl[i].clone()).collect())
+}
+
+//Splits the points by the line from the lowest leftmost to the highest rightmost. Below it go
+//left to right in the lower chain, and above it right to left in the upper one. Both chains are
+//monotone and lie on their own sides of the line, so they only meet at its ends. Points on the
+//line join the lower chain, unless nothing is above it and the upper chain would run along it
+fn monotone_order(mut pl: Vec<Point>) -> Vec<Point> {
+    pl.sort_by_key(|p| (p.x, p.y));
+    let (l, r) = (pl[0].clone(), pl[pl.len() - 1].clone());
+    let on_line_up = pl.iter().all(|p| Point::cross(&l, &r, p) <= 0);
+    let (mut upper, lower): (Vec<Point>, Vec<Point>) = pl.into_iter().partition(|p| {
+        let side = Point::cross(&l, &r, p);
+        side > 0 || (on_line_up && side == 0 && *p != l && *p != r)
+    });
+    upper.reverse();
+    let mut r = lower;
+    r.append(&mut upper);
+    r
+}
+
+//Some three points in line, found as two points in the same direction from a third
+fn find_collinear(pl: &[Point]) -> Option<(Point, Point, Point)> {
+    for (i, p) in pl.iter().enumerate() {
+        let mut directions: HashMap<(i128, i128), usize> = HashMap::with_capacity(pl.len());
+        for (j, q) in pl.iter().enumerate().filter(|&(j, _)| j != i) {
+            if let Some(k) = directions.insert(p.line_direction(q), j) {
+                return Some((p.clone(), pl[k].clone(), pl[j].clone()));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod polygonize_tests {
+    use super::*;
+    use crate::fixtures::points;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    #[test]
+    fn test_polygonize() {
+        let mut rng = StdRng::seed_from_u64(49);
+        //y = x^2 mod 11, with no three in line
+        let pts: Vec<Point> = (0..10).map(|x| Point::new(x, x * x % 11)).collect();
+        for mode in [
+            PolygonizeMode::Star,
+            PolygonizeMode::XMonotone,
+            PolygonizeMode::TwoOpt,
+        ] {
+            let p = polygonize(pts.clone(), mode, &mut rng).unwrap();
+            let mut pl = p.get_point_list().clone();
+            pl.sort_by_key(|q| (q.x, q.y));
+            let mut expected = pts.clone();
+            expected.sort_by_key(|q| (q.x, q.y));
+            assert_eq!(pl, expected, "{:?}", mode);
+            let dcel = p.triangulate();
+            assert!(dcel.validate().is_empty(), "{:?}", mode);
+            assert_eq!(dcel.get_internal_faces().len(), 8, "{:?}", mode);
+        }
+    }
+    #[test]
+    fn test_polygonize_errors() {
+        let mut rng = StdRng::seed_from_u64(49);
+        let mode = PolygonizeMode::Star;
+        assert_eq!(
+            polygonize(points(&[(0, 0), (1, 0)]), mode, &mut rng).err(),
+            Some(PolygonizeError::TooFewPoints(2))
+        );
+        assert_eq!(
+            polygonize(points(&[(0, 0), (4, 0), (0, 4), (4, 0)]), mode, &mut rng).err(),
+            Some(PolygonizeError::DuplicatePoint(Point::new(4, 0)))
+        );
+        let line = points(&[(0, 0), (1, 1), (2, 2), (3, 3)]);
+        for mode in [
+            PolygonizeMode::Star,
+            PolygonizeMode::XMonotone,
+            PolygonizeMode::TwoOpt,
+        ] {
+            assert!(matches!(
+                polygonize(line.clone(), mode, &mut rng),
+                Err(PolygonizeError::CollinearPoints(..))
+            ));
+        }
+        //A point inside a square is fine, but one on its side is in line with two corners
+        let square = points(&[(0, 0), (4, 0), (4, 4), (0, 4), (2, 1)]);
+        assert!(polygonize(square, PolygonizeMode::XMonotone, &mut rng).is_ok());
+        let edge = points(&[(0, 0), (4, 0), (4, 4), (0, 4), (2, 0)]);
+        assert_eq!(
+            polygonize(edge, PolygonizeMode::TwoOpt, &mut rng).err(),
+            Some(PolygonizeError::CollinearPoints(
+                Point::new(0, 0),
+                Point::new(4, 0),
+                Point::new(2, 0)
+            ))
+        );
+    }
+}
@@ -1,7 +1,7 @@
 // GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
 // Date: April 14, 2022
 // primitives.rs - Basic code for points and edges
-#[derive(Hash, Debug, PartialEq, Eq)]
+#[derive(Hash, Debug, Clone, PartialEq, Eq)]
 pub struct Point {
     //TODO: Have generics
     pub x: isize,
@@ -23,21 +23,39 @@ impl Point {
     }
 
     pub fn orientation(p: &Point, q: &Point, r: &Point) -> PointOrientation {
-        let val = (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y);
+        let val =
+            (q.y - p.y) as i128 * (r.x - q.x) as i128 - (q.x - p.x) as i128 * (r.y - q.y) as i128;
         if val == 0 {
-            return PointOrientation::Collinear;
+            PointOrientation::Collinear
         } else if val > 0 {
-            return PointOrientation::Clockwise;
+            PointOrientation::Clockwise
         } else {
-            return PointOrientation::Counterclockwise;
+            PointOrientation::Counterclockwise
         }
     }
 
-    pub fn clone(&self) -> Point {
-        Self {
-            x: self.x,
-            y: self.y,
-        }
+    //Twice the signed area of p q r, positive when they turn counterclockwise. Exact while the
+    //coordinates stay below 2^62 in magnitude
+    pub fn cross(p: &Point, q: &Point, r: &Point) -> i128 {
+        (q.x - p.x) as i128 * (r.y - p.y) as i128 - (q.y - p.y) as i128 * (r.x - p.x) as i128
+    }
+
+    //Direction of the line through self and other in lowest terms, pointing up or else right,
+    //so that points in line with self give the same direction. The points must differ
+    pub fn line_direction(&self, other: &Point) -> (i128, i128) {
+        let (dx, dy) = ((other.x - self.x) as i128, (other.y - self.y) as i128);
+        let g = gcd(dx, dy) * if dy < 0 || (dy == 0 && dx < 0) { -1 } else { 1 };
+        (dx / g, dy / g)
+    }
+
+    //Counterclockwise angular order of a and b around centre, starting from the +x direction
+    pub fn angular_cmp(centre: &Point, a: &Point, b: &Point) -> std::cmp::Ordering {
+        let (ax, ay) = ((a.x - centre.x) as i128, (a.y - centre.y) as i128);
+        let (bx, by) = ((b.x - centre.x) as i128, (b.y - centre.y) as i128);
+        let half = |x: i128, y: i128| if y > 0 || (y == 0 && x > 0) { 0 } else { 1 };
+        half(ax, ay)
+            .cmp(&half(bx, by))
+            .then_with(|| (bx * ay).cmp(&(ax * by)))
     }
 }
 
@@ -48,7 +66,7 @@ pub enum PointOrientation {
     Collinear,
 }
 
-#[derive(Hash, Debug, PartialEq, Eq)]
+#[derive(Hash, Debug, Clone, PartialEq, Eq)]
 pub struct DirEdge {
     pub start: Point,
     pub end: Point,
@@ -61,25 +79,16 @@ impl DirEdge {
             end: end.clone(),
         }
     }
+    //Whether q lies on the closed segment
+    pub fn contains(&self, q: &Point) -> bool {
+        let (p, r) = (&self.start, &self.end);
+        Point::cross(p, r, q) == 0
+            && q.x <= p.x.max(r.x)
+            && q.x >= p.x.min(r.x)
+            && q.y <= p.y.max(r.y)
+            && q.y >= p.y.min(r.y)
+    }
     pub fn intersects(&self, other: &Self) -> bool {
-        fn on_segment(e: &DirEdge, q: &Point) -> bool {
-            let p = &e.start;
-            let r = &e.end;
-
-            use std::cmp::max;
-            use std::cmp::min;
-
-            if q.x <= max(p.x, r.x)
-                && q.x >= min(p.x, r.x)
-                && q.y <= max(p.y, r.y)
-                && q.y >= min(p.y, r.y)
-            {
-                return true;
-            } else {
-                return false;
-            }
-        }
-
         let e1 = self;
         let e2 = other;
 
@@ -99,21 +108,136 @@ impl DirEdge {
             return true;
         }
 
-        if o1 == PointOrientation::Collinear && on_segment(e1, p2) {
+        if e1.contains(p2) {
             return true;
         };
-        if o2 == PointOrientation::Collinear && on_segment(e1, q2) {
+        if e1.contains(q2) {
             return true;
         };
-        if o3 == PointOrientation::Collinear && on_segment(e2, p1) {
+        if e2.contains(p1) {
             return true;
         };
-        if o4 == PointOrientation::Collinear && on_segment(e2, q1) {
+        if e2.contains(q1) {
             return true;
         };
         false
     }
+
+    //Like intersects, but edges that only touch at a shared endpoint do not count
+    pub fn crosses(&self, other: &Self) -> bool {
+        let (a, b) = (&self.start, &self.end);
+        let shared = if a == &other.start || a == &other.end {
+            Some(a)
+        } else if b == &other.start || b == &other.end {
+            Some(b)
+        } else {
+            None
+        };
+        let s = match shared {
+            Some(s) => s,
+            None => return self.intersects(other),
+        };
+        let p = if s == a { b } else { a };
+        let q = if s == &other.start {
+            &other.end
+        } else {
+            &other.start
+        };
+        if p == q {
+            return true;
+        }
+        //Sharing an endpoint, they only overlap if they leave it in the same direction
+        Point::orientation(s, p, q) == PointOrientation::Collinear
+            && (p.x - s.x) as i128 * (q.x - s.x) as i128 + (p.y - s.y) as i128 * (q.y - s.y) as i128
+                > 0
+    }
+}
+
+pub(crate) fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+//Exact fraction, always kept in lowest terms with a positive denominator
+#[derive(Hash, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Self {
+        if den == 0 {
+            panic!("Zero denominator");
+        }
+        let g = gcd(num, den);
+        let sign = if den < 0 { -1 } else { 1 };
+        Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+    pub fn from_int(n: isize) -> Self {
+        Self {
+            num: n as i128,
+            den: 1,
+        }
+    }
+    pub fn get_num(&self) -> i128 {
+        self.num
+    }
+    pub fn get_den(&self) -> i128 {
+        self.den
+    }
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
 }
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+//Point whose coordinates need not be on the integer lattice, such as a crossing of two edges
+#[derive(Hash, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RationalPoint {
+    pub x: Rational,
+    pub y: Rational,
+}
+
+impl RationalPoint {
+    pub fn new(x: Rational, y: Rational) -> Self {
+        Self { x, y }
+    }
+    pub fn from_point(p: &Point) -> Self {
+        Self {
+            x: Rational::from_int(p.x),
+            y: Rational::from_int(p.y),
+        }
+    }
+    pub fn to_f64(&self) -> (f64, f64) {
+        (self.x.to_f64(), self.y.to_f64())
+    }
+    //Nearest lattice point after multiplying by scale, rounding halves up
+    pub fn round(&self, scale: isize) -> Point {
+        let r = |v: &Rational| {
+            (2 * v.get_num() * scale as i128 + v.get_den()).div_euclid(2 * v.get_den()) as isize
+        };
+        Point::new(r(&self.x), r(&self.y))
+    }
+}
+
 #[cfg(test)]
 mod edge_tests {
     use super::*;
@@ -156,4 +280,21 @@ mod edge_tests {
         let t = [0, 0, 100, 0, 50, 0, 1, 1];
         tester(&t, true);
     }
+    #[test]
+    fn test_crossing() {
+        let e = |x1, y1, x2, y2| DirEdge::from_points(&Point::new(x1, y1), &Point::new(x2, y2));
+        assert!(!e(0, 0, 10, 0).crosses(&e(10, 0, 10, 10)));
+        assert!(!e(0, 0, 10, 0).crosses(&e(0, 0, -5, 0)));
+        assert!(e(0, 0, 10, 0).crosses(&e(0, 0, 5, 0)));
+        assert!(e(0, 0, 10, 0).crosses(&e(10, 0, 0, 0)));
+        assert!(e(0, 0, 10, 0).crosses(&e(5, -5, 5, 5)));
+        assert!(e(0, 0, 10, 0).crosses(&e(5, 0, 5, 5)));
+    }
+    #[test]
+    fn test_rational() {
+        assert_eq!(Rational::new(4, -6), Rational::new(-2, 3));
+        assert_eq!(Rational::new(0, -5), Rational::from_int(0));
+        assert!(Rational::new(1, 3) < Rational::new(1, 2));
+        assert!(Rational::new(-1, 2) < Rational::new(-1, 3));
+    }
 }
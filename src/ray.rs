@@ -0,0 +1,248 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// ray.rs - Ray shooting inside a polygon by walking its triangulation
+use crate::dcel::{DCELFaceKey, DCEL};
+use crate::location::{strictly_inside, SlabLocator};
+use crate::polygon::SimplePolygon;
+use crate::primitives::{Point, Rational, RationalPoint};
+use crate::visibility::in_wedge;
+use std::collections::HashMap;
+
+//Part of the boundary where a ray leaves the polygon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    //The inside of the polygon edge from vertex i to vertex i + 1
+    Edge(usize),
+    Vertex(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RayHit {
+    pub boundary: Boundary,
+    pub point: RationalPoint,
+}
+
+//Triangulation and point locator kept for answering many rays in one polygon
+pub struct RayShooter {
+    point_list: Vec<Point>,
+    dcel: DCEL,
+    locator: SlabLocator,
+}
+
+impl RayShooter {
+    pub fn new(polygon: &SimplePolygon) -> Self {
+        let dcel = polygon.triangulate();
+        let locator = dcel.slab_locator();
+        Self {
+            point_list: polygon.get_point_list().clone(),
+            dcel,
+            locator,
+        }
+    }
+
+    //First point of the boundary met by the ray from origin along direction. The origin must be
+    //strictly inside the polygon and the direction non zero
+    pub fn shoot(&self, origin: &Point, direction: &Point) -> Option<RayHit> {
+        if !self.is_valid(origin, direction) {
+            return None;
+        }
+        let face = self.locator.locate(origin).unwrap();
+        Some(self.walk(origin, direction, face))
+    }
+
+    //Answers a batch of (origin, direction) rays, locating each distinct origin only once, which
+    //suits a fan of rays cast from a few positions
+    pub fn shoot_all(&self, rays: &[(Point, Point)]) -> Vec<Option<RayHit>> {
+        let mut faces: HashMap<&Point, DCELFaceKey> = HashMap::new();
+        rays.iter()
+            .map(|(o, d)| {
+                if !self.is_valid(o, d) {
+                    return None;
+                }
+                let face = *faces
+                    .entry(o)
+                    .or_insert_with(|| self.locator.locate(o).unwrap());
+                Some(self.walk(o, d, face))
+            })
+            .collect()
+    }
+
+    fn is_valid(&self, origin: &Point, direction: &Point) -> bool {
+        (direction.x, direction.y) != (0, 0) && strictly_inside(&self.point_list, origin)
+    }
+
+    //Carries the ray on through vertex x when it only grazes it, into the triangle it crosses
+    //next. Stops it at the first vertex where it leaves the interior, which is Err
+    fn pass_vertex(&self, mut x: usize, d: &Point) -> Result<DCELFaceKey, RayHit> {
+        let external = self.dcel.get_external_face().get_key();
+        loop {
+            let p = &self.point_list[x];
+            if !in_wedge(&self.point_list, x, &Point::new(p.x + d.x, p.y + d.y)) {
+                return Err(RayHit {
+                    boundary: Boundary::Vertex(x),
+                    point: RationalPoint::from_point(p),
+                });
+            }
+            //Positive when q is left of the ray
+            let side =
+                |q: &Point| d.x as i128 * (q.y - p.y) as i128 - d.y as i128 * (q.x - p.x) as i128;
+            let mut along = None;
+            for e in self.dcel.get_outgoing_edges(p) {
+                let (_, a) = self.dcel.get_edge_points(e);
+                if side(&a) == 0
+                    && d.x as i128 * (a.x - p.x) as i128 + d.y as i128 * (a.y - p.y) as i128 > 0
+                {
+                    along = Some(self.dcel.get_edge_indices(e).1);
+                    break;
+                }
+                let face = self.dcel.get_edge_face(e);
+                //The corner of the triangle at p runs counterclockwise from a to b
+                let (b, _) = self.dcel.get_prev_edge_points(e);
+                if face != external && side(&a) < 0 && side(&b) > 0 {
+                    return Ok(face);
+                }
+            }
+            //The ray runs along a diagonal to the vertex at its other end
+            x = along.unwrap();
+        }
+    }
+
+    //Walks from the triangle holding o into the neighbour across the side the ray leaves by,
+    //until that side is an edge of the polygon. All the vertices of the triangulation lie on the
+    //boundary, so the walk meets one whenever the ray touches the boundary on the way, and goes
+    //on past it if the ray stays inside
+    fn walk(&self, o: &Point, d: &Point, start: DCELFaceKey) -> RayHit {
+        //Positive when p is left of the ray
+        let side =
+            |p: &Point| d.x as i128 * (p.y - o.y) as i128 - d.y as i128 * (p.x - o.x) as i128;
+        let along =
+            |p: &Point| d.x as i128 * (p.x - o.x) as i128 + d.y as i128 * (p.y - o.y) as i128;
+        let external = self.dcel.get_external_face().get_key();
+        let mut face = start;
+        loop {
+            let mut exit = None;
+            let mut vertex = None;
+            for e in self.dcel.get_face_cycles(face).swap_remove(0) {
+                let (u, v) = self.dcel.get_edge_points(e);
+                let (su, sv) = (side(&u), side(&v));
+                let (iu, iv) = self.dcel.get_edge_indices(e);
+                if su == 0 && sv == 0 {
+                    //Only possible from an origin on a diagonal, running along it to the end
+                    vertex = Some(if along(&u) > along(&v) { iu } else { iv });
+                    break;
+                }
+                //Sides running from the right of the ray to its left are the one it leaves by,
+                //or the one it came in by seen from the other triangle
+                if su <= 0 && sv >= 0 {
+                    exit = Some((e, u, v, su, sv));
+                }
+            }
+            let x = match vertex {
+                Some(x) => x,
+                None => {
+                    let (e, u, v, su, sv) = exit.unwrap();
+                    let (iu, iv) = self.dcel.get_edge_indices(e);
+                    if su == 0 {
+                        iu
+                    } else if sv == 0 {
+                        iv
+                    } else {
+                        let next = self.dcel.get_edge_face(self.dcel.get_twin_edge(e));
+                        if next != external {
+                            face = next;
+                            continue;
+                        }
+                        //o + t d meets the edge at t = num / den
+                        let (ex, ey) = ((v.x - u.x) as i128, (v.y - u.y) as i128);
+                        let num = (u.x - o.x) as i128 * ey - (u.y - o.y) as i128 * ex;
+                        let den = d.x as i128 * ey - d.y as i128 * ex;
+                        let at = |p: isize, dp: isize| {
+                            Rational::new(p as i128 * den + num * dp as i128, den)
+                        };
+                        return RayHit {
+                            boundary: Boundary::Edge(iu),
+                            point: RationalPoint::new(at(o.x, d.x), at(o.y, d.y)),
+                        };
+                    }
+                }
+            };
+            match self.pass_vertex(x, d) {
+                Ok(next) => face = next,
+                Err(hit) => return hit,
+            }
+        }
+    }
+}
+
+impl SimplePolygon {
+    //For a single ray. Build a RayShooter instead to reuse the triangulation across many
+    pub fn shoot_ray(&self, origin: &Point, direction: &Point) -> Option<RayHit> {
+        RayShooter::new(self).shoot(origin, direction)
+    }
+}
+
+#[cfg(test)]
+mod ray_tests {
+    use super::*;
+    use crate::fixtures::{comb, notched_square};
+    #[test]
+    fn test_ray_shooting() {
+        let p = notched_square();
+        let shooter = RayShooter::new(&p);
+        let hit = |o: (isize, isize), d: (isize, isize)| {
+            let r = shooter
+                .shoot(&Point::new(o.0, o.1), &Point::new(d.0, d.1))
+                .unwrap();
+            (r.boundary, r.point.x, r.point.y)
+        };
+        let int = Rational::from_int;
+        let edge = Boundary::Edge;
+        assert_eq!(hit((2, 8), (1, 0)), (edge(5), int(4), int(8)));
+        assert_eq!(
+            hit((2, 8), (3, -1)),
+            (edge(5), int(4), Rational::new(22, 3))
+        );
+        assert_eq!(hit((2, 8), (0, -1)), (edge(0), int(2), int(0)));
+        //Passing under the notch to the far wall
+        assert_eq!(hit((5, 2), (1, 1)), (edge(1), int(10), int(7)));
+        //Grazing the corner of the notch and going on below it
+        assert_eq!(hit((2, 8), (1, -2)), (edge(0), int(6), int(0)));
+        //Into the corner of the notch, beyond which lies the outside
+        assert_eq!(hit((1, 1), (1, 1)), (Boundary::Vertex(5), int(4), int(4)));
+        //Along the bottom of the notch, which is boundary from the corner on
+        assert_eq!(hit((2, 4), (1, 0)), (Boundary::Vertex(5), int(4), int(4)));
+
+        let rays = vec![
+            (Point::new(8, 8), Point::new(-1, 0)),
+            (Point::new(8, 8), Point::new(0, 0)),
+            (Point::new(5, 8), Point::new(1, 0)),
+        ];
+        let hits = shooter.shoot_all(&rays);
+        assert_eq!(hits[0].as_ref().unwrap().boundary, Boundary::Edge(3));
+        assert_eq!(hits[1..], [None, None]);
+        assert_eq!(p.shoot_ray(&Point::new(8, 8), &Point::new(-1, 0)), hits[0]);
+    }
+    #[test]
+    fn test_ray_in_comb() {
+        let shooter = RayShooter::new(&comb());
+        let hit = |o: (isize, isize), d: (isize, isize)| {
+            let r = shooter
+                .shoot(&Point::new(o.0, o.1), &Point::new(d.0, d.1))
+                .unwrap();
+            (r.boundary, r.point.x, r.point.y)
+        };
+        let int = Rational::from_int;
+        let edge = Boundary::Edge;
+        assert_eq!(hit((1, 1), (0, 1)), (edge(8), int(1), Rational::new(7, 2)));
+        assert_eq!(hit((8, 3), (1, 0)), (edge(4), Rational::new(46, 5), int(3)));
+        //Under the teeth to the far corner, which stops the ray
+        assert_eq!(
+            hit((1, 1), (11, -1)),
+            (Boundary::Vertex(1), int(12), int(0))
+        );
+        //Along y = 1 past every tooth tip to the far wall
+        assert_eq!(hit((1, 1), (1, 0)), (edge(1), int(12), int(1)));
+        //From a gap past the tip at (6, 1) and on to the bottom
+        assert_eq!(hit((4, 3), (1, -1)), (edge(0), int(7), int(0)));
+    }
+}
@@ -0,0 +1,122 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// sample.rs - Uniformly distributed random points inside a polygon
+use crate::polygon::SimplePolygon;
+use crate::primitives::{Point, Rational};
+use rand::Rng;
+
+//Triangles of a triangulation with the running total of their doubled areas, kept for drawing
+//many points from one polygon. Any Rng can drive it, so a seeded one gives repeatable samples
+pub struct PolygonSampler {
+    triangles: Vec<[Point; 3]>,
+    //Twice the area of the triangles up to and including each one
+    cumulative: Vec<i128>,
+    //Twice the area of the polygon, which is never zero
+    total: i128,
+}
+
+impl PolygonSampler {
+    //None for a polygon of zero area, such as one whose vertices are all in line, which has no
+    //interior to draw from
+    pub fn new(polygon: &SimplePolygon) -> Option<Self> {
+        if polygon.signed_area() == Rational::from_int(0) {
+            return None;
+        }
+        let dcel = polygon.triangulate();
+        let mut triangles = Vec::new();
+        let mut cumulative = Vec::new();
+        let mut total = 0;
+        for face in dcel.get_internal_faces() {
+            let t = dcel.get_point_list(face);
+            let (a, b, c) = (&t[0], &t[1], &t[2]);
+            total += ((b.x - a.x) as i128 * (c.y - a.y) as i128
+                - (b.y - a.y) as i128 * (c.x - a.x) as i128)
+                .abs();
+            cumulative.push(total);
+            triangles.push([a.clone(), b.clone(), c.clone()]);
+        }
+        Some(Self {
+            triangles,
+            cumulative,
+            total,
+        })
+    }
+
+    //A triangle is picked with probability proportional to its area, exactly as the areas are
+    //integers, and then a point in it by folding the unit square onto the half below its diagonal
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+        let pick = rng.gen_range(0..self.total);
+        let [a, b, c] = &self.triangles[self.cumulative.partition_point(|&s| s <= pick)];
+        let (mut u, mut v): (f64, f64) = (rng.gen(), rng.gen());
+        if u + v > 1.0 {
+            (u, v) = (1.0 - u, 1.0 - v);
+        }
+        (
+            a.x as f64 + u * (b.x - a.x) as f64 + v * (c.x - a.x) as f64,
+            a.y as f64 + u * (b.y - a.y) as f64 + v * (c.y - a.y) as f64,
+        )
+    }
+
+    pub fn sample_n<R: Rng + ?Sized>(&self, rng: &mut R, n: usize) -> Vec<(f64, f64)> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+}
+
+impl SimplePolygon {
+    //For a single batch. Build a PolygonSampler instead to reuse the triangulation
+    pub fn sample_points<R: Rng + ?Sized>(&self, rng: &mut R, n: usize) -> Option<Vec<(f64, f64)>> {
+        Some(PolygonSampler::new(self)?.sample_n(rng, n))
+    }
+}
+
+#[cfg(test)]
+mod sample_tests {
+    use super::*;
+    use crate::fixtures::{notched_square, points};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    #[test]
+    fn test_sampler() {
+        let p = notched_square();
+        let sampler = PolygonSampler::new(&p).unwrap();
+        let pts = sampler.sample_n(&mut StdRng::seed_from_u64(7), 4000);
+        assert_eq!(
+            Some(pts.clone()),
+            p.sample_points(&mut StdRng::seed_from_u64(7), 4000)
+        );
+        for &(x, y) in &pts {
+            assert!((0.0..=10.0).contains(&x) && (0.0..=10.0).contains(&y));
+            assert!(!(x > 4.0 && x < 6.0 && y > 4.0));
+        }
+        //The part left of the notch holds 40 of the 88 units of area
+        let left = pts.iter().filter(|&&(x, _)| x < 4.0).count() as f64 / pts.len() as f64;
+        assert!((left - 40.0 / 88.0).abs() < 0.03);
+    }
+    #[test]
+    fn test_sampler_triangle() {
+        //A single triangle, where a quarter of the area lies below x + y = 6 and the mean is
+        //the centroid
+        let p = SimplePolygon::from_point_list(vec![
+            Point::new(0, 0),
+            Point::new(12, 0),
+            Point::new(0, 12),
+        ]);
+        let pts = p
+            .sample_points(&mut StdRng::seed_from_u64(11), 4000)
+            .unwrap();
+        assert!(pts
+            .iter()
+            .all(|&(x, y)| x >= 0.0 && y >= 0.0 && x + y <= 12.0));
+        let near = pts.iter().filter(|&&(x, y)| x + y < 6.0).count() as f64 / pts.len() as f64;
+        assert!((near - 0.25).abs() < 0.03);
+        let mean_x = pts.iter().map(|&(x, _)| x).sum::<f64>() / pts.len() as f64;
+        assert!((mean_x - 4.0).abs() < 0.2);
+    }
+    #[test]
+    fn test_sampler_flat() {
+        //Three points in line bound no area to draw from
+        let flat = SimplePolygon::from_point_list(points(&[(0, 0), (2, 2), (4, 4)]));
+        assert!(PolygonSampler::new(&flat).is_none());
+        assert_eq!(flat.sample_points(&mut StdRng::seed_from_u64(3), 10), None);
+    }
+}
@@ -0,0 +1,411 @@
+// GroupID-6 (18117106_18114083) - Suraaj K S & Yashaswi Jaiswal
+// Date: October 19, 2026
+// visibility.rs - Region of a polygon that can be seen from a point
+use crate::location::strictly_inside;
+use crate::polygon::{RationalPolygon, SimplePolygon};
+use crate::primitives::{DirEdge, Point, Rational, RationalPoint};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+#[derive(Debug, PartialEq)]
+pub enum VisibilityError {
+    //The viewpoint has to be strictly inside the polygon
+    NotInside,
+    //Rounding at this scale merges vertices of the region or makes its boundary cross itself
+    ScaleTooSmall,
+}
+
+//Polygon edge crossed by the sweep ray. Edges of a simple polygon never cross, so two edges
+//that meet the ray at the same time are in the same order along every ray they both meet,
+//which lets them be compared without knowing the ray
+struct ActiveEdge<'a> {
+    q: &'a Point,
+    a: &'a Point,
+    b: &'a Point,
+    index: usize,
+}
+
+impl ActiveEdge<'_> {
+    //Whether self is nearer to q than other, along the rays that meet both
+    fn is_nearer(&self, other: &Self) -> bool {
+        let side = |a: &Point, b: &Point, p: &Point| Point::cross(a, b, p).signum();
+        let sq = side(self.a, self.b, self.q);
+        let (sc, sd) = (side(self.a, self.b, other.a), side(self.a, self.b, other.b));
+        if sc * sd >= 0 && (sc, sd) != (0, 0) {
+            //other is on one side of the line through self, and is farther if q is not there
+            return (sc + sd).signum() != sq;
+        }
+        //Otherwise other straddles that line, so self is on one side of the line through other
+        let tq = side(other.a, other.b, self.q);
+        let (sa, sb) = (
+            side(other.a, other.b, self.a),
+            side(other.a, other.b, self.b),
+        );
+        (sa + sb).signum() == tq
+    }
+
+    //Where the ray from q through d meets the line through the edge
+    fn hit(&self, d: &Point) -> RationalPoint {
+        let (q, a, b) = (self.q, self.a, self.b);
+        let (dx, dy) = ((d.x - q.x) as i128, (d.y - q.y) as i128);
+        let (ex, ey) = ((b.x - a.x) as i128, (b.y - a.y) as i128);
+        let num = (q.x - a.x) as i128 * dy - (q.y - a.y) as i128 * dx;
+        let den = ex * dy - ey * dx;
+        RationalPoint::new(
+            Rational::new(a.x as i128 * den + ex * num, den),
+            Rational::new(a.y as i128 * den + ey * num, den),
+        )
+    }
+}
+
+impl PartialEq for ActiveEdge<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for ActiveEdge<'_> {}
+
+impl PartialOrd for ActiveEdge<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ActiveEdge<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.index == other.index {
+            Ordering::Equal
+        } else if self.is_nearer(other) {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }
+}
+
+//Whether p lies strictly inside the interior angle of the polygon at vertex i
+pub(crate) fn in_wedge(pl: &[Point], i: usize, p: &Point) -> bool {
+    let n = pl.len();
+    in_angle(&pl[(i + n - 1) % n], &pl[i], &pl[(i + 1) % n], p)
+}
+
+//Whether p lies strictly inside the angle at a on the left of a boundary running prev, a, next.
+//When the boundary doubles back along a dangling edge the angle is a full turn less that edge
+pub(crate) fn in_angle(prev: &Point, a: &Point, next: &Point, p: &Point) -> bool {
+    let dot = |u: &Point, v: &Point| {
+        (u.x - a.x) as i128 * (v.x - a.x) as i128 + (u.y - a.y) as i128 * (v.y - a.y) as i128
+    };
+    match Point::cross(prev, a, next).signum() {
+        1 => Point::cross(a, next, p) > 0 && Point::cross(a, p, prev) > 0,
+        -1 => !(Point::cross(a, prev, p) >= 0 && Point::cross(a, p, next) >= 0),
+        _ if dot(prev, next) > 0 => Point::cross(a, prev, p) != 0 || dot(prev, p) < 0,
+        _ => Point::cross(a, next, p) > 0,
+    }
+}
+
+impl SimplePolygon {
+    //Whether the open segment between vertices i and j lies in the interior. Polygon edges and
+    //segments that touch the boundary anywhere in between are not diagonals
+    pub fn is_diagonal(&self, i: usize, j: usize) -> bool {
+        let pl = self.get_point_list();
+        let n = pl.len();
+        if i == j || (i + 1) % n == j || (j + 1) % n == i || !in_wedge(pl, i, &pl[j]) {
+            return false;
+        }
+        let segment = DirEdge::from_points(&pl[i], &pl[j]);
+        (0..n).all(|k| {
+            let side = DirEdge::from_points(&pl[k], &pl[(k + 1) % n]);
+            if [i, j].contains(&k) || [i, j].contains(&((k + 1) % n)) {
+                !segment.crosses(&side)
+            } else {
+                !segment.intersects(&side)
+            }
+        })
+    }
+
+    //Pairs of vertices that see each other, meaning they are joined by a polygon edge or a
+    //diagonal, as sorted adjacency lists. Each vertex sweeps a ray around itself as in
+    //visibility_polygon, so this takes O(n^2 log n) rather than the O(n^3) of testing every pair
+    pub fn visibility_graph(&self) -> Vec<Vec<usize>> {
+        let pl = self.get_point_list();
+        let n = pl.len();
+        let mut r: Vec<Vec<usize>> = (0..n).map(|i| vec![(i + n - 1) % n, (i + 1) % n]).collect();
+        for i in 0..n {
+            let q = &pl[i];
+            let edge = |k: usize| ActiveEdge {
+                q,
+                a: &pl[k],
+                b: &pl[(k + 1) % n],
+                index: k,
+            };
+            //Edges through q never turn around it, so only the other edges can block
+            let sweeps = |k: usize| Point::cross(q, &pl[k], &pl[(k + 1) % n]) > 0;
+            let dist = |k: usize| {
+                let (dx, dy) = ((pl[k].x - q.x) as i128, (pl[k].y - q.y) as i128);
+                dx * dx + dy * dy
+            };
+
+            let mut order: Vec<usize> = (0..n).filter(|&k| k != i).collect();
+            order.sort_by(|&a, &b| Point::angular_cmp(q, &pl[a], &pl[b]));
+            let d = &pl[order[0]];
+            let mut active = BTreeSet::new();
+            for k in (0..n).filter(|&k| sweeps(k)) {
+                let (a, b) = (&pl[k], &pl[(k + 1) % n]);
+                let ends_on_ray = Point::cross(q, b, d) == 0
+                    && (b.x - q.x) as i128 * (d.x - q.x) as i128
+                        + (b.y - q.y) as i128 * (d.y - q.y) as i128
+                        > 0;
+                if (Point::cross(q, a, d) > 0 && Point::cross(q, d, b) > 0) || ends_on_ray {
+                    active.insert(edge(k));
+                }
+            }
+
+            let mut start = 0;
+            while start < order.len() {
+                let mut end = start + 1;
+                while end < order.len()
+                    && Point::angular_cmp(q, &pl[order[start]], &pl[order[end]]).is_eq()
+                {
+                    end += 1;
+                }
+                let group = &order[start..end];
+                for &k in group {
+                    let prev = (k + n - 1) % n;
+                    if sweeps(prev) {
+                        active.remove(&edge(prev));
+                    }
+                }
+                //Only the nearest vertex on the ray can be seen, and only if it comes before
+                //every edge the ray crosses
+                let j = *group.iter().min_by_key(|&&k| dist(k)).unwrap();
+                let blocked = active.first().is_some_and(|e| {
+                    Point::cross(e.a, e.b, &pl[j]).signum() != Point::cross(e.a, e.b, q).signum()
+                });
+                let adjacent = (i + 1) % n == j || (j + 1) % n == i;
+                if !blocked && !adjacent && in_wedge(pl, i, &pl[j]) {
+                    r[i].push(j);
+                }
+                for &k in group {
+                    if sweeps(k) {
+                        active.insert(edge(k));
+                    }
+                }
+                start = end;
+            }
+        }
+        for v in &mut r {
+            v.sort_unstable();
+            v.dedup();
+        }
+        r
+    }
+
+    //Region seen from q, which has to be strictly inside, with every vertex multiplied by scale
+    //and rounded to the nearest lattice point. Vertices where a sight line meets an edge are
+    //generally not lattice points, so scale 1 keeps the input coordinates but moves those
+    //vertices by up to half a unit, and larger scales trade coordinate size for accuracy.
+    //Fails with ScaleTooSmall when the rounded boundary is no longer simple
+    pub fn visibility_polygon(
+        &self,
+        q: &Point,
+        scale: isize,
+    ) -> Result<SimplePolygon, VisibilityError> {
+        self.visibility_region(q)
+            .ok_or(VisibilityError::NotInside)?
+            .to_simple_polygon(scale)
+            .ok_or(VisibilityError::ScaleTooSmall)
+    }
+
+    //Exact region seen from q. Vertices are swept in angular order around q, keeping the edges
+    //crossed by the sweep ray ordered by distance, so this takes O(n log n)
+    pub(crate) fn visibility_region(&self, q: &Point) -> Option<RationalPolygon> {
+        let pl = self.get_point_list();
+        let n = pl.len();
+        if !strictly_inside(pl, q) {
+            return None;
+        }
+        //Edge i runs from vertex i to vertex i + 1, and is only ever crossed by the ray if it
+        //turns counterclockwise around q
+        let edge = |i: usize| ActiveEdge {
+            q,
+            a: &pl[i],
+            b: &pl[(i + 1) % n],
+            index: i,
+        };
+        let sweeps = |i: usize| Point::cross(q, &pl[i], &pl[(i + 1) % n]) > 0;
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| Point::angular_cmp(q, &pl[i], &pl[j]));
+
+        //Edges crossed by the ray just before it reaches the first vertex
+        let d = &pl[order[0]];
+        let mut active = BTreeSet::new();
+        for i in (0..n).filter(|&i| sweeps(i)) {
+            let (a, b) = (&pl[i], &pl[(i + 1) % n]);
+            let ends_on_ray = Point::cross(q, b, d) == 0
+                && (b.x - q.x) as i128 * (d.x - q.x) as i128
+                    + (b.y - q.y) as i128 * (d.y - q.y) as i128
+                    > 0;
+            if (Point::cross(q, a, d) > 0 && Point::cross(q, d, b) > 0) || ends_on_ray {
+                active.insert(edge(i));
+            }
+        }
+
+        let mut r: Vec<RationalPoint> = Vec::new();
+        let mut start = 0;
+        while start < n {
+            let mut end = start + 1;
+            while end < n && Point::angular_cmp(q, &pl[order[start]], &pl[order[end]]).is_eq() {
+                end += 1;
+            }
+            let group = &order[start..end];
+            let d = &pl[group[0]];
+
+            let before = active.first().unwrap().index;
+            for &i in group {
+                let prev = (i + n - 1) % n;
+                if sweeps(prev) {
+                    active.remove(&edge(prev));
+                }
+            }
+            for &i in group {
+                if sweeps(i) {
+                    active.insert(edge(i));
+                }
+            }
+            let after = active.first().unwrap().index;
+
+            //The boundary jumps along the ray from the edge seen before to the one seen after
+            if before != after {
+                for p in [edge(before).hit(d), edge(after).hit(d)] {
+                    if r.last() != Some(&p) {
+                        r.push(p);
+                    }
+                }
+            }
+            start = end;
+        }
+        if r.len() > 1 && r.first() == r.last() {
+            r.pop();
+        }
+        Some(RationalPolygon::from_point_list(r))
+    }
+}
+
+#[cfg(test)]
+mod visibility_tests {
+    use super::*;
+    use crate::fixtures::{comb, notched_square, points};
+    #[test]
+    fn test_visibility_polygon() {
+        let p = notched_square();
+        let v = p.visibility_polygon(&Point::new(2, 2), 1).unwrap();
+        let expected = points(&[(10, 6), (6, 4), (4, 4), (4, 10), (0, 10), (0, 0), (10, 0)]);
+        assert_eq!(v.get_point_list(), &expected);
+
+        //From inside the right arm, the left arm is hidden behind the notch
+        let v = p.visibility_region(&Point::new(8, 7)).unwrap();
+        assert!(v.get_point_list().contains(&RationalPoint::new(
+            Rational::new(10, 3),
+            Rational::from_int(0)
+        )));
+        assert_eq!(v.get_point_list().len(), 5);
+        let v = p.visibility_polygon(&Point::new(8, 7), 3).unwrap();
+        assert!(v.get_point_list().contains(&Point::new(10, 0)));
+        assert_eq!(v.get_point_list().len(), 5);
+
+        for q in [Point::new(5, 8), Point::new(5, 4)] {
+            assert_eq!(
+                p.visibility_polygon(&q, 1).unwrap_err(),
+                VisibilityError::NotInside
+            );
+        }
+    }
+    #[test]
+    fn test_visibility_polygon_scale() {
+        //Seen from (7, 9) the sight line past (4, 8) meets the left side at (0, 20 / 3)
+        let p = SimplePolygon::from_point_list(points(&[
+            (0, 0),
+            (8, 0),
+            (8, 10),
+            (6, 10),
+            (4, 8),
+            (0, 10),
+        ]));
+        let q = Point::new(7, 9);
+        let v = p.visibility_region(&q).unwrap();
+        assert!(v.get_point_list().contains(&RationalPoint::new(
+            Rational::from_int(0),
+            Rational::new(20, 3)
+        )));
+        let v = p.visibility_polygon(&q, 1).unwrap();
+        assert_eq!(v.get_point_list()[3], Point::new(0, 7));
+        let v = p.visibility_polygon(&q, 3).unwrap();
+        assert_eq!(v.get_point_list()[3], Point::new(0, 20));
+        assert_eq!(v.get_point_list()[0], Point::new(24, 30));
+        assert_eq!(
+            p.visibility_polygon(&q, 0).unwrap_err(),
+            VisibilityError::ScaleTooSmall
+        );
+
+        //From (5, 2) the sight line past (3, 3) ends at (2, 7 / 2), which rounds up onto the
+        //line back to (4, 2), so the boundary doubles back on itself without merging vertices
+        let p = SimplePolygon::from_point_list(points(&[
+            (4, 2),
+            (9, 0),
+            (11, 5),
+            (3, 4),
+            (3, 3),
+            (1, 5),
+            (9, 6),
+            (0, 5),
+        ]));
+        let q = Point::new(5, 2);
+        assert_eq!(
+            p.visibility_polygon(&q, 1).unwrap_err(),
+            VisibilityError::ScaleTooSmall
+        );
+        let v = p.visibility_polygon(&q, 2).unwrap();
+        assert!(v.get_point_list().contains(&Point::new(4, 7)));
+    }
+    #[test]
+    fn test_visibility_graph() {
+        let p = notched_square();
+        assert!(p.is_diagonal(0, 4));
+        assert!(!p.is_diagonal(0, 1));
+        assert!(!p.is_diagonal(2, 7));
+        //Cuts through the notch
+        assert!(!p.is_diagonal(5, 3));
+        //Touches (4, 4) on the way
+        assert!(!p.is_diagonal(0, 2));
+
+        let g = p.visibility_graph();
+        let n = p.get_point_list().len();
+        for (i, seen) in g.iter().enumerate() {
+            for j in 0..n {
+                let edge = (i + 1) % n == j || (j + 1) % n == i;
+                assert_eq!(seen.contains(&j), edge || p.is_diagonal(i, j));
+            }
+        }
+        assert_eq!(g[2], vec![1, 3, 4]);
+    }
+    #[test]
+    fn test_visibility_along_tooth_tips() {
+        //The ray along y = 1 grazes all three tips, so from left of them only the strip below
+        //and the first gap can be seen
+        let v = comb().visibility_polygon(&Point::new(1, 1), 1).unwrap();
+        let expected = points(&[(12, 1), (2, 1), (0, 6), (0, 0), (12, 0)]);
+        assert_eq!(v.get_point_list(), &expected);
+
+        let p = comb();
+        let g = p.visibility_graph();
+        for (i, seen) in g.iter().enumerate() {
+            assert!(seen
+                .iter()
+                .all(|&j| p.is_diagonal(i, j) || (i + 1) % 10 == j || (j + 1) % 10 == i));
+        }
+        //Tip to tip along y = 1 is blocked by the tip in between
+        assert!(p.is_diagonal(8, 6) && !p.is_diagonal(8, 4));
+    }
+}